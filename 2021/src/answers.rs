@@ -0,0 +1,171 @@
+//! Known correct answers, read from a small subset of TOML:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 1791
+//! part2 = "a string answer"
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::registry::Day;
+use crate::{AocError, ParseError, ParseResult, Result};
+
+#[derive(Clone, Debug, Default)]
+pub struct ExpectedAnswers {
+  answers: HashMap<(u8, u8), String>,
+}
+
+#[derive(Debug)]
+pub enum Verdict {
+  Pass(String),
+  Mismatch { expected: String, actual: String },
+  Unverified(String),
+  Failed(AocError),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+  pub day: u8,
+  pub part: u8,
+  pub verdict: Verdict,
+}
+
+impl ExpectedAnswers {
+  pub fn default_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "answers.toml"]
+      .iter()
+      .collect()
+  }
+
+  pub fn load(path: &Path) -> Result<Self> {
+    let toml = fs::read_to_string(path).map_err(|err| {
+      io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+    })?;
+    Ok(Self::parse(&toml)?)
+  }
+
+  pub fn parse(toml: &str) -> ParseResult<Self> {
+    let mut answers = HashMap::new();
+    let mut day = None;
+
+    for line in toml.lines().map(str::trim) {
+      let bad_line =
+        || ParseError::at(toml, line, "`[dayNN]` or `partN = answer`");
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      if let Some(table) = line.strip_prefix('[') {
+        let number = table
+          .strip_suffix(']')
+          .and_then(|table| table.strip_prefix("day"))
+          .and_then(|number| number.parse().ok())
+          .ok_or_else(bad_line)?;
+        day = Some(number);
+      } else {
+        let (key, value) = line.split_once('=').ok_or_else(bad_line)?;
+        let part = match key.trim() {
+          "part1" => 1,
+          "part2" => 2,
+          _ => return Err(bad_line()),
+        };
+        let day = day.ok_or_else(bad_line)?;
+        let value = parse_value(value.trim()).ok_or_else(bad_line)?;
+        answers.insert((day, part), value);
+      }
+    }
+
+    Ok(Self { answers })
+  }
+
+  pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+    self.answers.get(&(day, part)).map(String::as_str)
+  }
+}
+
+/// Runs both parts of `day` against `input` and compares the results with
+/// the expected answers.
+pub fn verify(
+  day: &Day,
+  input: &str,
+  expected: &ExpectedAnswers,
+) -> [Verification; 2] {
+  [1, 2].map(|part| {
+    let actual = day.solve(part, input).map(|answer| answer.value);
+    let verdict = match (actual, expected.get(day.number, part)) {
+      (Err(err), _) => Verdict::Failed(err),
+      (Ok(actual), None) => Verdict::Unverified(actual),
+      (Ok(actual), Some(expected)) if actual == expected => {
+        Verdict::Pass(actual)
+      }
+      (Ok(actual), Some(expected)) => Verdict::Mismatch {
+        expected: expected.to_string(),
+        actual,
+      },
+    };
+
+    Verification {
+      day: day.number,
+      part,
+      verdict,
+    }
+  })
+}
+
+impl Verdict {
+  pub fn is_ok(&self) -> bool {
+    matches!(self, Verdict::Pass(_) | Verdict::Unverified(_))
+  }
+}
+
+impl fmt::Display for Verification {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    write!(formatter, "Day {:02}, part {}: ", self.day, self.part)?;
+    match &self.verdict {
+      Verdict::Pass(actual) => write!(formatter, "pass ({})", one_line(actual)),
+      Verdict::Mismatch { expected, actual } => write!(
+        formatter,
+        "MISMATCH, expected {}, got {}",
+        one_line(expected),
+        one_line(actual)
+      ),
+      Verdict::Unverified(actual) => {
+        write!(formatter, "no expected answer ({})", one_line(actual))
+      }
+      Verdict::Failed(err) => write!(formatter, "FAILED, {}", err),
+    }
+  }
+}
+
+fn one_line(answer: &str) -> String {
+  answer.escape_default().to_string()
+}
+
+fn parse_value(value: &str) -> Option<String> {
+  if let Some(string) = value.strip_prefix('"') {
+    let mut chars = string.strip_suffix('"')?.chars();
+    let mut unescaped = String::new();
+    while let Some(chr) = chars.next() {
+      unescaped.push(match chr {
+        '\\' => match chars.next()? {
+          'n' => '\n',
+          chr @ ('"' | '\\') => chr,
+          _ => return None,
+        },
+        '"' => return None,
+        chr => chr,
+      });
+    }
+    Some(unescaped)
+  } else if value.bytes().all(|b| b.is_ascii_digit() || b == b'-') {
+    value.parse::<i64>().ok().map(|n| n.to_string())
+  } else {
+    None
+  }
+}
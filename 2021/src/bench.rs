@@ -0,0 +1,155 @@
+//! Timings of the parse, part 1 and part 2 stages of a [`Solution`].
+
+use std::fmt;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::{Result, Solution};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Settings {
+  pub warmup: usize,
+  pub iterations: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Timings {
+  samples: Vec<Duration>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StageTimings {
+  pub parse: Timings,
+  pub part1: Timings,
+  pub part2: Timings,
+}
+
+#[derive(Clone, Debug)]
+pub struct Report {
+  pub day: u8,
+  pub timings: StageTimings,
+}
+
+impl Default for Settings {
+  fn default() -> Self {
+    Self {
+      warmup: 2,
+      iterations: 10,
+    }
+  }
+}
+
+pub fn bench<S: Solution>(
+  input: &str,
+  settings: Settings,
+) -> Result<StageTimings> {
+  let parsed = S::parse(input)?;
+  Ok(StageTimings {
+    parse: measure(settings, || S::parse(input))?,
+    part1: measure(settings, || S::part1(&parsed))?,
+    part2: measure(settings, || S::part2(&parsed))?,
+  })
+}
+
+/// Runs `f` `settings.warmup` times without recording anything, then
+/// records the duration of `settings.iterations` more runs.
+pub fn measure<T, F>(settings: Settings, mut f: F) -> Result<Timings>
+where
+  F: FnMut() -> Result<T>,
+{
+  for _ in 0..settings.warmup {
+    black_box(f()?);
+  }
+
+  let mut samples = Vec::with_capacity(settings.iterations);
+  for _ in 0..settings.iterations.max(1) {
+    let start = Instant::now();
+    let result = f()?;
+    samples.push(start.elapsed());
+    black_box(result);
+  }
+  samples.sort();
+
+  Ok(Timings { samples })
+}
+
+impl Timings {
+  pub fn min(&self) -> Duration {
+    self.samples[0]
+  }
+
+  pub fn median(&self) -> Duration {
+    let mid = self.samples.len() / 2;
+    if self.samples.len().is_multiple_of(2) {
+      (self.samples[mid - 1] + self.samples[mid]) / 2
+    } else {
+      self.samples[mid]
+    }
+  }
+
+  pub fn mean(&self) -> Duration {
+    self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+  }
+
+  fn to_json(&self) -> String {
+    format!(
+      r#"{{"min_ns": {}, "median_ns": {}, "mean_ns": {}}}"#,
+      self.min().as_nanos(),
+      self.median().as_nanos(),
+      self.mean().as_nanos()
+    )
+  }
+}
+
+impl StageTimings {
+  pub fn stages(&self) -> [(&'static str, &Timings); 3] {
+    [
+      ("parse", &self.parse),
+      ("part1", &self.part1),
+      ("part2", &self.part2),
+    ]
+  }
+}
+
+impl fmt::Display for Report {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    for (i, (stage, timings)) in self.timings.stages().iter().enumerate() {
+      if i == 0 {
+        write!(formatter, "Day {:02}  ", self.day)?;
+      } else {
+        formatter.write_str("\n        ")?;
+      }
+      write!(
+        formatter,
+        "{}  min {:>10.1?}  median {:>10.1?}  mean {:>10.1?}",
+        stage,
+        timings.min(),
+        timings.median(),
+        timings.mean()
+      )?;
+    }
+    Ok(())
+  }
+}
+
+/// Renders reports as JSON, with all durations in nanoseconds.
+pub fn to_json(settings: Settings, reports: &[Report]) -> String {
+  let mut json = format!(
+    "{{\n  \"warmup\": {},\n  \"iterations\": {},\n  \"days\": [",
+    settings.warmup, settings.iterations
+  );
+
+  for (i, report) in reports.iter().enumerate() {
+    let separator = if i == 0 { "" } else { "," };
+    write!(json, "{}\n    {{\"day\": {}", separator, report.day).unwrap();
+    for (stage, timings) in report.timings.stages() {
+      write!(json, ", \"{}\": {}", stage, timings.to_json()).unwrap();
+    }
+    json.push('}');
+  }
+
+  json.push_str("\n  ]\n}\n");
+  json
+}
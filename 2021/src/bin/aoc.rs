@@ -0,0 +1,253 @@
+use adventofcode_2021::answers::{self, ExpectedAnswers};
+use adventofcode_2021::bench;
+use adventofcode_2021::registry::{self, Day, DAYS};
+use adventofcode_2021::solution::Flags;
+use adventofcode_2021::{AocError, Result};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+const USAGE: &str = "\
+Usage: aoc run <day> [--part <1|2>] [--input <path>]
+       aoc verify [<day>] [--answers <path>]
+       aoc bench [<day>] [--iterations <n>] [--warmup <n>] [--json <path>]
+
+run     Runs the solution for <day>, reading the puzzle input from
+        data/day-NN unless --input is given. Both parts are run unless
+        --part is given. Some days accept extra flags instead of --part,
+        see `aoc help`.
+verify  Runs all solutions, or the one for <day>, against data/day-NN and
+        compares the results with the answers in answers.toml.
+bench   Times parsing and both parts of all solutions, or the one for <day>,
+        on data/day-NN. Each stage runs --warmup times (default 2) before
+        --iterations (default 10) timed runs. --json also writes the
+        timings to <path>, or to stdout if <path> is -.";
+
+fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  match run(&args) {
+    Ok(true) => {}
+    Ok(false) => process::exit(1),
+    Err(err) => {
+      eprintln!("Error: {}", err);
+      if let AocError::Unsupported(_) = err {
+        eprintln!("\n{}", USAGE);
+      }
+      process::exit(1);
+    }
+  }
+}
+
+fn run(args: &[String]) -> Result<bool> {
+  match args.split_first() {
+    Some((command, args)) if command == "run" => run_day(args).map(|_| true),
+    Some((command, args)) if command == "verify" => verify(args),
+    Some((command, args)) if command == "bench" => bench(args).map(|_| true),
+    Some((command, _)) if command == "help" || command == "--help" => {
+      help();
+      Ok(true)
+    }
+    Some((command, _)) => {
+      Err(usage_error(&format!("Unknown command {}", command)))
+    }
+    None => Err(usage_error("Missing command")),
+  }
+}
+
+fn help() {
+  println!("{}", USAGE);
+  for day in DAYS.iter().filter(|day| !day.flags().is_empty()) {
+    println!("\nFlags for aoc run {}:", day.number);
+    for flag in day.flags() {
+      println!("  {}", flag);
+    }
+  }
+}
+
+fn run_day(args: &[String]) -> Result<()> {
+  // The day's own flags are only known once the day is, so they can only
+  // follow it.
+  let day_flags = args
+    .first()
+    .and_then(|arg| arg.parse().ok())
+    .and_then(registry::find)
+    .map_or(&[][..], |day| day.flags());
+  let mut valued = vec!["--part", "--input"];
+  let mut switches = vec![];
+  for flag in day_flags {
+    match flag.value {
+      Some(_) => valued.push(flag.name),
+      None => switches.push(flag.name),
+    }
+  }
+
+  let command_line = CommandLine::parse(args, &valued, &switches)?;
+  let number = command_line.day.ok_or_else(|| usage_error("Missing day"))?;
+  let day = registry::find(number).ok_or_else(|| registry::bad_day(number))?;
+  let input = registry::read_input(day, command_line.path("--input"))?;
+
+  let flags = Flags::new(
+    command_line
+      .flags
+      .iter()
+      .filter(|(name, _)| day_flags.iter().any(|flag| flag.name == *name))
+      .copied()
+      .collect(),
+  );
+  if !flags.is_empty() {
+    if command_line.flag("--part").is_some() {
+      return Err(usage_error("--part cannot be combined with day flags"));
+    }
+    return day.run(&input, &flags, &mut io::stdout().lock());
+  }
+
+  let parts = match command_line.flag("--part") {
+    Some(part) => vec![parse_number(part)?],
+    None => vec![1, 2],
+  };
+
+  for part in parts {
+    println!("{}", day.solve(part, &input)?);
+  }
+
+  Ok(())
+}
+
+fn verify(args: &[String]) -> Result<bool> {
+  let command_line = CommandLine::parse(args, &["--answers"], &[])?;
+  let expected = ExpectedAnswers::load(
+    &command_line
+      .path("--answers")
+      .unwrap_or_else(ExpectedAnswers::default_path),
+  )?;
+  let (mut passed, mut failed, mut unverified) = (0, 0, 0);
+  for day in command_line.days()? {
+    let input = match registry::read_input(day, None) {
+      Ok(input) => input,
+      Err(err) => {
+        println!("Day {:02}: FAILED, {}", day.number, err);
+        failed += 2;
+        continue;
+      }
+    };
+
+    for verification in answers::verify(day, &input, &expected) {
+      println!("{}", verification);
+      match verification.verdict {
+        answers::Verdict::Pass(_) => passed += 1,
+        answers::Verdict::Unverified(_) => unverified += 1,
+        _ => failed += 1,
+      }
+    }
+  }
+
+  println!(
+    "\n{} passed, {} failed, {} without expected answer",
+    passed, failed, unverified
+  );
+  Ok(failed == 0)
+}
+
+fn bench(args: &[String]) -> Result<()> {
+  let command_line =
+    CommandLine::parse(args, &["--iterations", "--warmup", "--json"], &[])?;
+  let mut settings = bench::Settings::default();
+  if let Some(iterations) = command_line.flag("--iterations") {
+    settings.iterations = parse_number(iterations)?;
+  }
+  if let Some(warmup) = command_line.flag("--warmup") {
+    settings.warmup = parse_number(warmup)?;
+  }
+  let json = command_line.flag("--json");
+
+  let mut reports = vec![];
+  for day in command_line.days()? {
+    let input = registry::read_input(day, None)?;
+    let report = day.bench(&input, settings)?;
+    if json != Some("-") {
+      println!("{}", report);
+    }
+    reports.push(report);
+  }
+
+  match json {
+    Some("-") => print!("{}", bench::to_json(settings, &reports)),
+    Some(path) => fs::write(path, bench::to_json(settings, &reports))?,
+    None => {}
+  }
+
+  Ok(())
+}
+
+/// Command line arguments after the command: an optional day and flags
+/// that either take a value or are switches without one.
+struct CommandLine<'a> {
+  day: Option<u8>,
+  flags: Vec<(&'a str, Option<&'a str>)>,
+}
+
+impl<'a> CommandLine<'a> {
+  fn parse(
+    args: &'a [String],
+    valued: &[&str],
+    switches: &[&str],
+  ) -> Result<Self> {
+    let mut day = None;
+    let mut flags = vec![];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+      if valued.contains(&arg.as_str()) {
+        let value = args
+          .next()
+          .ok_or_else(|| usage_error(&format!("Missing value for {}", arg)))?;
+        flags.push((arg.as_str(), Some(value.as_str())));
+      } else if switches.contains(&arg.as_str()) {
+        flags.push((arg.as_str(), None));
+      } else if day.is_none() && !arg.starts_with("--") {
+        day = Some(parse_number(arg)?);
+      } else {
+        return Err(usage_error(&format!("Unexpected argument {}", arg)));
+      }
+    }
+
+    Ok(Self { day, flags })
+  }
+
+  fn flag(&self, name: &str) -> Option<&'a str> {
+    self
+      .flags
+      .iter()
+      .rev()
+      .find(|(flag, _)| *flag == name)
+      .and_then(|(_, value)| *value)
+  }
+
+  fn path(&self, name: &str) -> Option<PathBuf> {
+    self.flag(name).map(PathBuf::from)
+  }
+
+  /// The given day, or all days if none was given.
+  fn days(&self) -> Result<Vec<&'static Day>> {
+    match self.day {
+      Some(number) => Ok(vec![
+        registry::find(number).ok_or_else(|| registry::bad_day(number))?
+      ]),
+      None => Ok(DAYS.iter().collect()),
+    }
+  }
+}
+
+fn parse_number<T: FromStr>(arg: &str) -> Result<T> {
+  arg
+    .parse()
+    .map_err(|_| usage_error(&format!("Not a number: {}", arg)))
+}
+
+fn usage_error(message: &str) -> AocError {
+  AocError::Unsupported(message.to_string())
+}
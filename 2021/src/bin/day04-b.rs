@@ -1,7 +1,6 @@
-use adventofcode_2021::day04::*;
-use std::io;
+use adventofcode_2021::registry;
+use std::process::ExitCode;
 
-fn main() -> io::Result<()> {
-  let stdin = io::stdin();
-  run(stdin.lock(), BingoResultType::Loser)
+fn main() -> ExitCode {
+  registry::run_stdin(4, &[2])
 }
@@ -1,13 +1,6 @@
-use std::io;
+use adventofcode_2021::registry;
+use std::process::ExitCode;
 
-use adventofcode_2021::day06::{count_fish, read_fish};
-
-fn main() -> io::Result<()> {
-  let stdin = io::stdin();
-  println!(
-    "Produced {} fish in 256 days",
-    count_fish(read_fish(stdin.lock())?, 256)
-  );
-
-  Ok(())
+fn main() -> ExitCode {
+  registry::run_stdin(6, &[2])
 }
@@ -0,0 +1,209 @@
+//! Sets of small numbers or grid positions stored as one bit each.
+
+use crate::grid::Coord;
+use std::fmt;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The numbers below a fixed capacity.
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct BitSet {
+  words: Vec<u64>,
+  capacity: usize,
+}
+
+impl BitSet {
+  /// An empty set for the numbers below `capacity`.
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      words: vec![0; capacity.div_ceil(WORD_BITS)],
+      capacity,
+    }
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn get(&self, bit: usize) -> bool {
+    let (word, mask) = self.locate(bit);
+    self.words[word] & mask != 0
+  }
+
+  pub fn set(&mut self, bit: usize) {
+    let (word, mask) = self.locate(bit);
+    self.words[word] |= mask;
+  }
+
+  pub fn clear(&mut self, bit: usize) {
+    let (word, mask) = self.locate(bit);
+    self.words[word] &= !mask;
+  }
+
+  pub fn toggle(&mut self, bit: usize) {
+    let (word, mask) = self.locate(bit);
+    self.words[word] ^= mask;
+  }
+
+  /// The number of set bits.
+  pub fn count(&self) -> usize {
+    self
+      .words
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.iter().all(|&word| word == 0)
+  }
+
+  /// Whether every bit below the capacity is set.
+  pub fn is_full(&self) -> bool {
+    self.count() == self.capacity
+  }
+
+  /// The set bits in ascending order.
+  pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    self.words.iter().enumerate().flat_map(|(i, &word)| {
+      let mut rest = word;
+      std::iter::from_fn(move || {
+        if rest == 0 {
+          return None;
+        }
+        let bit = rest.trailing_zeros() as usize;
+        rest &= rest - 1;
+        Some(i * WORD_BITS + bit)
+      })
+    })
+  }
+
+  /// Sets every bit set in `other`, which needs the same capacity.
+  pub fn union_with(&mut self, other: &BitSet) {
+    self.assert_same_capacity(other);
+    for (word, other) in self.words.iter_mut().zip(&other.words) {
+      *word |= other;
+    }
+  }
+
+  /// Clears every bit not set in `other`, which needs the same capacity.
+  pub fn intersect_with(&mut self, other: &BitSet) {
+    self.assert_same_capacity(other);
+    for (word, other) in self.words.iter_mut().zip(&other.words) {
+      *word &= other;
+    }
+  }
+
+  fn locate(&self, bit: usize) -> (usize, u64) {
+    assert!(
+      bit < self.capacity,
+      "bit {} out of bounds, capacity is {}",
+      bit,
+      self.capacity
+    );
+    (bit / WORD_BITS, 1 << (bit % WORD_BITS))
+  }
+
+  fn assert_same_capacity(&self, other: &BitSet) {
+    assert_eq!(self.capacity, other.capacity, "capacities differ");
+  }
+}
+
+impl fmt::Debug for BitSet {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.debug_set().entries(self.iter()).finish()
+  }
+}
+
+/// Positions in a rectangle, stored row by row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BitGrid {
+  width: usize,
+  height: usize,
+  bits: BitSet,
+}
+
+impl BitGrid {
+  /// An empty set for a rectangle of the given size.
+  pub fn new(width: usize, height: usize) -> Self {
+    Self {
+      width,
+      height,
+      bits: BitSet::new(width * height),
+    }
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, (row, col): Coord) -> bool {
+    row < self.height && col < self.width
+  }
+
+  pub fn get(&self, coord: Coord) -> bool {
+    self.bits.get(self.index(coord))
+  }
+
+  pub fn set(&mut self, coord: Coord) {
+    self.bits.set(self.index(coord))
+  }
+
+  pub fn clear(&mut self, coord: Coord) {
+    self.bits.clear(self.index(coord))
+  }
+
+  pub fn toggle(&mut self, coord: Coord) {
+    self.bits.toggle(self.index(coord))
+  }
+
+  pub fn count(&self) -> usize {
+    self.bits.count()
+  }
+
+  /// The set positions, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+    let width = self.width;
+    self.bits.iter().map(move |bit| (bit / width, bit % width))
+  }
+
+  /// Sets every position set in `other`, which needs the same size.
+  pub fn union_with(&mut self, other: &BitGrid) {
+    self.assert_same_size(other);
+    self.bits.union_with(&other.bits);
+  }
+
+  /// Clears every position not set in `other`, which needs the same size.
+  pub fn intersect_with(&mut self, other: &BitGrid) {
+    self.assert_same_size(other);
+    self.bits.intersect_with(&other.bits);
+  }
+
+  /// The positions as bits of a `BitSet`, row by row.
+  pub fn as_bit_set(&self) -> &BitSet {
+    &self.bits
+  }
+
+  fn index(&self, (row, col): Coord) -> usize {
+    assert!(
+      self.contains((row, col)),
+      "{:?} out of bounds, size is {}x{}",
+      (row, col),
+      self.width,
+      self.height
+    );
+    row * self.width + col
+  }
+
+  fn assert_same_size(&self, other: &BitGrid) {
+    assert_eq!(
+      (self.width, self.height),
+      (other.width, other.height),
+      "sizes differ"
+    );
+  }
+}
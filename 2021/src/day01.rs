@@ -0,0 +1,37 @@
+use crate::parse::Parser;
+use crate::{ParseResult, Result, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+  type Input = Vec<u32>;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<u32>> {
+    let parser = Parser::new(input);
+    Ok(
+      parser
+        .lines()
+        .map(|line| parser.number(line.trim(), "a depth"))
+        .collect::<ParseResult<_>>()?,
+    )
+  }
+
+  fn part1(depths: &Vec<u32>) -> Result<usize> {
+    Ok(count_increases(depths, 1))
+  }
+
+  fn part2(depths: &Vec<u32>) -> Result<usize> {
+    Ok(count_increases(depths, 3))
+  }
+}
+
+// Two sliding windows of `window_size` share all but their first and last
+// element, so comparing those is enough to tell whether the sum increased.
+pub fn count_increases(depths: &[u32], window_size: usize) -> usize {
+  depths
+    .windows(window_size + 1)
+    .filter(|w| w[window_size] > w[0])
+    .count()
+}
@@ -0,0 +1,72 @@
+use crate::parse::Parser;
+use crate::{ParseResult, Result, Solution};
+
+pub struct Day02;
+
+pub enum Command {
+  Forward(u32),
+  Up(u32),
+  Down(u32),
+}
+
+impl Solution for Day02 {
+  type Input = Vec<Command>;
+  type Answer1 = u32;
+  type Answer2 = u32;
+
+  fn parse(input: &str) -> Result<Vec<Command>> {
+    let parser = Parser::new(input);
+    Ok(
+      parser
+        .lines()
+        .map(|line| parse_command(&parser, line))
+        .collect::<ParseResult<_>>()?,
+    )
+  }
+
+  fn part1(commands: &Vec<Command>) -> Result<u32> {
+    let mut position = [0, 0];
+
+    for command in commands {
+      match command {
+        Command::Forward(amount) => position[0] += amount,
+        Command::Up(amount) => position[1] -= amount,
+        Command::Down(amount) => position[1] += amount,
+      }
+    }
+
+    Ok(position[0] * position[1])
+  }
+
+  fn part2(commands: &Vec<Command>) -> Result<u32> {
+    let mut aim = 0;
+    let mut depth = 0;
+    let mut position = 0;
+
+    for command in commands {
+      match command {
+        Command::Forward(amount) => {
+          position += amount;
+          depth += amount * aim;
+        }
+        Command::Up(amount) => aim -= amount,
+        Command::Down(amount) => aim += amount,
+      }
+    }
+
+    Ok(position * depth)
+  }
+}
+
+pub fn parse_command(parser: &Parser, line: &str) -> ParseResult<Command> {
+  const COMMANDS: &str = "`forward`, `up` or `down`";
+  let [command, amount] = parser.words(line, [COMMANDS, "an amount"])?;
+  let amount = parser.number(amount, "an amount")?;
+
+  match command {
+    "forward" => Ok(Command::Forward(amount)),
+    "up" => Ok(Command::Up(amount)),
+    "down" => Ok(Command::Down(amount)),
+    _ => Err(parser.error(command, COMMANDS)),
+  }
+}
@@ -0,0 +1,92 @@
+use crate::{AocError, ParseError, ParseResult, Result, Solution};
+use partition::partition;
+
+pub struct Day03;
+
+#[derive(Clone, Debug)]
+pub struct DiagnosticReport {
+  pub width: usize,
+  pub numbers: Vec<u32>,
+}
+
+impl Solution for Day03 {
+  type Input = DiagnosticReport;
+  type Answer1 = u32;
+  type Answer2 = u32;
+
+  fn parse(input: &str) -> Result<DiagnosticReport> {
+    let width = input
+      .find(char::is_whitespace)
+      .ok_or_else(|| ParseError::at_end(input, "a line break"))?;
+    let numbers = input
+      .lines()
+      .map(|line| parse_line(input, line.trim_end(), width))
+      .collect::<ParseResult<_>>()?;
+
+    Ok(DiagnosticReport { width, numbers })
+  }
+
+  fn part1(report: &DiagnosticReport) -> Result<u32> {
+    let mut gamma = 0u32;
+    for bit in (0..report.width).rev() {
+      let count = bit_count_for_mask(report.numbers.iter(), 1 << bit);
+      gamma = (gamma << 1) | (count >= 0) as u32;
+    }
+
+    let epsilon = !gamma & ((1 << report.width) - 1);
+
+    Ok(epsilon * gamma)
+  }
+
+  fn part2(report: &DiagnosticReport) -> Result<u32> {
+    let mut current_mask: u32 = 1 << (report.width - 1);
+    let mut numbers = report.numbers.clone();
+    let bit_count = bit_count_for_mask(numbers.iter(), current_mask);
+
+    let is_oxy = |x: &u32, mask: u32, bit_count: i32| {
+      ((x & mask) != 0) == (bit_count >= 0)
+    };
+    let (mut oxy, mut co2) =
+      partition(&mut numbers, |x| is_oxy(x, current_mask, bit_count));
+
+    while {
+      current_mask >>= 1;
+      current_mask > 0
+    } {
+      if oxy.len() > 1 {
+        let oxy_bit_count = bit_count_for_mask(oxy.iter(), current_mask);
+        oxy = partition(oxy, |x| is_oxy(x, current_mask, oxy_bit_count)).0;
+      }
+
+      if co2.len() > 1 {
+        let co2_bit_count = bit_count_for_mask(co2.iter(), current_mask);
+        co2 = partition(co2, |x| is_oxy(x, current_mask, co2_bit_count)).1;
+      }
+    }
+
+    match oxy.first() {
+      None => Err(AocError::NoAnswer("no oxy result".to_string())),
+      Some(o) => match co2.first() {
+        None => Err(AocError::NoAnswer("no co2 result".to_string())),
+        Some(c) => Ok(o * c),
+      },
+    }
+  }
+}
+
+fn value_for_mask(n: u32, bit_mask: u32) -> i32 {
+  [-1, 1][(n & bit_mask != 0) as usize]
+}
+
+fn bit_count_for_mask(xs: std::slice::Iter<u32>, bit_mask: u32) -> i32 {
+  xs.fold(0, |v, x| v + value_for_mask(*x, bit_mask))
+}
+
+fn parse_line(input: &str, digits: &str, width: usize) -> ParseResult<u32> {
+  let expected = || format!("{} binary digits", width);
+  if digits.len() != width {
+    return Err(ParseError::at(input, digits, expected()));
+  }
+  u32::from_str_radix(digits, 2)
+    .map_err(|_| ParseError::at(input, digits, expected()))
+}
@@ -1,7 +1,8 @@
+use crate::bitset::BitSet;
+use crate::parse::Parser;
+use crate::{AocError, ParseResult, Result, Solution};
 use std::fmt;
 use std::fmt::Write;
-use std::io;
-use std::io::BufRead;
 use std::str::FromStr;
 use std::vec::Vec;
 
@@ -12,79 +13,95 @@ pub enum BingoResultType {
   Loser,
 }
 
-pub fn run(
-  mut stdin_lock: io::StdinLock,
-  result_type: BingoResultType,
-) -> io::Result<()> {
-  let mut buffer = String::new();
+pub struct Day04;
 
-  let numbers = bingo_numbers(&mut stdin_lock, &mut buffer)?;
-  let mut boards = bingo_boards(&mut stdin_lock, &mut buffer)?;
-  let mut finished = BitSet::with_size(boards.len());
+#[derive(Clone, Debug)]
+pub struct Bingo {
+  pub numbers: Vec<BingoNumber>,
+  pub boards: Vec<BingoBoard>,
+}
+
+impl Solution for Day04 {
+  type Input = Bingo;
+  type Answer1 = u32;
+  type Answer2 = u32;
+
+  fn parse(input: &str) -> Result<Bingo> {
+    let parser = Parser::new(input);
+    let sections = parser.sections();
+    let (numbers, boards) = sections
+      .split_first()
+      .ok_or_else(|| parser.error_at_end("bingo numbers"))?;
+
+    Ok(Bingo {
+      numbers: bingo_numbers(&parser, numbers)?,
+      boards: bingo_boards(&parser, boards.iter().flat_map(|b| b.lines()))?,
+    })
+  }
+
+  fn part1(bingo: &Bingo) -> Result<u32> {
+    play(bingo, BingoResultType::Winner)
+  }
+
+  fn part2(bingo: &Bingo) -> Result<u32> {
+    play(bingo, BingoResultType::Loser)
+  }
+}
+
+pub fn play(bingo: &Bingo, result_type: BingoResultType) -> Result<u32> {
+  let mut boards = bingo.boards.clone();
+  let mut finished = BitSet::new(boards.len());
 
-  for n in numbers {
-    for (board, i) in (&mut boards).iter_mut().zip(0..) {
-      if !finished.is_set(i) && board.check(n) {
-        finished.set(i, true);
+  for &n in &bingo.numbers {
+    for (board, i) in boards.iter_mut().zip(0..) {
+      if !finished.get(i) && board.check(n) {
+        finished.set(i);
 
         if match result_type {
           BingoResultType::Winner => true,
-          BingoResultType::Loser => finished.all(),
+          BingoResultType::Loser => finished.is_full(),
         } {
-          let sum_unmarked = board.sum_unmarked();
           let num: u32 = n.as_u8().into();
-          println!(
-            "{} on {}, unmarked {}\nResult {}\n{}",
-            if finished.all() { "Lost" } else { "Won" },
-            n.as_u8(),
-            sum_unmarked,
-            sum_unmarked * num,
-            board
-          );
-          return Ok(());
+          return Ok(board.sum_unmarked() * num);
         }
       }
     }
   }
 
-  Err(io::Error::new(io::ErrorKind::InvalidData, "no board wins"))
+  Err(AocError::NoAnswer("no board wins".to_string()))
 }
 
-fn bingo_numbers(
-  stdin: &mut io::StdinLock<'_>,
-  buffer: &mut String,
-) -> io::Result<Vec<BingoNumber>> {
-  stdin.read_line(buffer)?;
-
-  buffer.trim().split(',').map(bingo_number).collect()
+pub fn bingo_numbers(
+  parser: &Parser,
+  line: &str,
+) -> ParseResult<Vec<BingoNumber>> {
+  parser.separated(line.trim(), ",", |str| bingo_number(parser, str))
 }
 
-fn bingo_number(str: &str) -> io::Result<BingoNumber> {
-  match u8::from_str(str) {
-    Err(_) => Err(bad_input(&format!("Not a number: {}", str))),
-    Ok(n) => BingoNumber::try_from(n)
-      .map_err(|_| bad_input(&format!("Not a valid bingo number: {}", n))),
-  }
+pub fn bingo_number(parser: &Parser, str: &str) -> ParseResult<BingoNumber> {
+  let expected =
+    || format!("a bingo number from 0 to {}", BingoNumber::max_value());
+  u8::from_str(str)
+    .ok()
+    .and_then(|n| BingoNumber::try_from(n).ok())
+    .ok_or_else(|| parser.error(str, expected()))
 }
 
-fn bingo_boards(
-  stdin: &mut io::StdinLock,
-  buffer: &mut String,
-) -> io::Result<Vec<BingoBoard>> {
+pub fn bingo_boards<'a>(
+  parser: &Parser,
+  lines: impl Iterator<Item = &'a str>,
+) -> ParseResult<Vec<BingoBoard>> {
   let mut board_index = BingoBoardIndex::new();
   let mut boards = Vec::with_capacity(100);
   let mut current_board = append_bingo_board(&mut boards);
   let mut did_wrap = BingoBoardIndexWrap::DidNotWrap;
 
-  while {
-    buffer.clear();
-    stdin.read_line(buffer)? > 0
-  } {
-    for token in buffer.trim().split_whitespace() {
+  for line in lines {
+    for token in line.split_whitespace() {
       if let BingoBoardIndexWrap::DidWrap = did_wrap {
         current_board = append_bingo_board(&mut boards);
       }
-      current_board.set(board_index, bingo_number(token)?);
+      current_board.set(board_index, bingo_number(parser, token)?);
       did_wrap = board_index.advance();
     }
   }
@@ -92,14 +109,10 @@ fn bingo_boards(
   if let BingoBoardIndexWrap::DidWrap = did_wrap {
     Ok(boards)
   } else {
-    println!("{}", boards.last().unwrap());
-    Err(io::Error::new(
-      io::ErrorKind::InvalidData,
-      format!(
-        "Found only {} numbers, need {} to fill a board:",
-        board_index, BINGO_BOARD_SIZE
-      ),
-    ))
+    Err(parser.error_at_end(format!(
+      "{} more numbers to fill the last board",
+      BINGO_BOARD_SIZE - board_index.as_usize()
+    )))
   }
 }
 
@@ -108,12 +121,8 @@ fn append_bingo_board(boards: &mut Vec<BingoBoard>) -> &mut BingoBoard {
   boards.last_mut().unwrap()
 }
 
-fn bad_input(str: &str) -> io::Error {
-  io::Error::new(io::ErrorKind::InvalidData, str)
-}
-
 #[derive(Clone, Debug)]
-struct BingoBoard {
+pub struct BingoBoard {
   set: u32,
   numbers: BingoNumberMap,
 }
@@ -155,8 +164,14 @@ impl BingoBoard {
   }
 }
 
+impl Default for BingoBoard {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 #[derive(Clone, Copy, Debug)]
-struct BingoNumber {
+pub struct BingoNumber {
   num: u8,
 }
 
@@ -173,7 +188,7 @@ impl BingoNumber {
   }
 }
 
-struct BingoNumberTryFromError();
+pub struct BingoNumberTryFromError();
 impl TryFrom<u8> for BingoNumber {
   type Error = BingoNumberTryFromError;
   fn try_from(num: u8) -> Result<Self, BingoNumberTryFromError> {
@@ -185,8 +200,8 @@ impl TryFrom<u8> for BingoNumber {
   }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct BingoBoardIndex {
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BingoBoardIndex {
   idx: u8,
 }
 
@@ -220,7 +235,7 @@ impl fmt::Display for BingoBoardIndex {
   }
 }
 
-struct BingoBoardIndexTryFromError();
+pub struct BingoBoardIndexTryFromError();
 impl TryFrom<u8> for BingoBoardIndex {
   type Error = BingoBoardIndexTryFromError;
   fn try_from(idx: u8) -> Result<Self, BingoBoardIndexTryFromError> {
@@ -232,7 +247,7 @@ impl TryFrom<u8> for BingoBoardIndex {
   }
 }
 
-enum BingoBoardIndexWrap {
+pub enum BingoBoardIndexWrap {
   DidNotWrap,
   DidWrap,
 }
@@ -244,7 +259,7 @@ struct BingoNumberMap {
 
 impl BingoNumberMap {
   const SIZE: usize = (BingoNumber::max_value() + 1) as usize;
-  const UNSET: u8 = u8::max_value();
+  const UNSET: u8 = u8::MAX;
 
   pub fn new() -> Self {
     Self {
@@ -320,49 +335,3 @@ fn bit(set: u32, shift: usize) -> char {
     _ => ' ',
   }
 }
-
-#[derive(Debug)]
-struct BitSet {
-  bits: Vec<u64>,
-  size: usize,
-}
-
-impl BitSet {
-  pub fn with_size(size: usize) -> Self {
-    Self {
-      bits: vec![0; (size + 63) / 64],
-      size,
-    }
-  }
-
-  pub fn is_set(&self, bit: usize) -> bool {
-    let (idx, lshift) = (bit / 64, bit % 64);
-    if idx < self.bits.len() {
-      self.bits[idx] & (1 << lshift) != 0
-    } else {
-      false
-    }
-  }
-
-  pub fn set(&mut self, bit: usize, value: bool) {
-    let (idx, lshift) = (bit / 64, bit % 64);
-    if idx < self.bits.len() {
-      if value {
-        self.bits[idx] |= 1 << lshift;
-      } else {
-        self.bits[idx] ^= 1 << lshift;
-      }
-    }
-  }
-
-  pub fn all(&self) -> bool {
-    let last = self.bits.len() - 1;
-    for n in &self.bits[..last] {
-      if *n != u64::max_value() {
-        return false;
-      }
-    }
-
-    self.bits[last] == (1u64 << (self.size % 64)) - 1
-  }
-}
@@ -0,0 +1,140 @@
+use crate::parse::Parser;
+use crate::{ParseResult, Result, Solution};
+use std::cmp::Ordering;
+use std::cmp::PartialOrd;
+use std::collections::HashMap;
+use std::iter::repeat;
+use std::ops;
+
+pub type Point = [u32; 2];
+pub type Vector = [Point; 2];
+
+pub struct Day05;
+
+impl Solution for Day05 {
+  type Input = Vec<Vector>;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  fn parse(input: &str) -> Result<Vec<Vector>> {
+    let parser = Parser::new(input);
+    Ok(
+      parser
+        .lines()
+        .map(|line| parse_line(&parser, line))
+        .collect::<ParseResult<_>>()?,
+    )
+  }
+
+  fn part1(lines: &Vec<Vector>) -> Result<usize> {
+    Ok(count_overlaps(lines, |[[x1, y1], [x2, y2]]| {
+      x1 == x2 || y1 == y2
+    }))
+  }
+
+  fn part2(lines: &Vec<Vector>) -> Result<usize> {
+    Ok(count_overlaps(lines, |_| true))
+  }
+}
+
+pub fn count_overlaps<F>(lines: &[Vector], include: F) -> usize
+where
+  F: Fn(&Vector) -> bool,
+{
+  let mut counts = IntersectionCounts::new();
+  for &line in lines.iter().filter(|line| include(line)) {
+    add_line(&mut counts, line);
+  }
+  counts.count_intersections()
+}
+
+#[derive(Default)]
+pub struct IntersectionCounts {
+  counts: HashMap<Point, u32>,
+}
+
+impl IntersectionCounts {
+  pub fn new() -> Self {
+    Self {
+      counts: HashMap::new(),
+    }
+  }
+
+  pub fn add_point(&mut self, point: Point) {
+    *self.counts.entry(point).or_insert(0) += 1
+  }
+
+  pub fn add_points<T>(&mut self, points: T)
+  where
+    T: Iterator<Item = (u32, u32)>,
+  {
+    for (x, y) in points {
+      self.add_point([x, y]);
+    }
+  }
+
+  pub fn count_intersections(&self) -> usize {
+    self.counts.values().filter(|&&v| v > 1).count()
+  }
+}
+
+pub fn parse_line(parser: &Parser, line: &str) -> ParseResult<Vector> {
+  let (from, to) =
+    parser.split(line.trim_end(), " -> ", "a line `x1,y1 -> x2,y2`")?;
+  Ok([parse_point(parser, from)?, parse_point(parser, to)?])
+}
+
+fn parse_point(parser: &Parser, point: &str) -> ParseResult<Point> {
+  let (x, y) = parser.split(point, ",", "a point `x,y`")?;
+  Ok([
+    parser.number(x, "a coordinate")?,
+    parser.number(y, "a coordinate")?,
+  ])
+}
+
+pub fn add_line(counts: &mut IntersectionCounts, [[x1, y1], [x2, y2]]: Vector) {
+  if x1 == x2 {
+    if y1 == y2 {
+      counts.add_point([x1, y1])
+    } else {
+      counts.add_points(repeat(x1).zip(range(y1, y2)))
+    }
+  } else if y1 == y2 {
+    counts.add_points(range(x1, x2).zip(repeat(y1)))
+  } else {
+    counts.add_points(range(x1, x2).zip(range(y1, y2)))
+  }
+}
+
+/// Inclusive range that counts down if `end` is smaller than the start.
+pub struct AnyDirRange<Idx> {
+  pub next: Option<Idx>,
+  pub end: Idx,
+}
+
+impl<Idx> Iterator for AnyDirRange<Idx>
+where
+  Idx: PartialOrd
+    + Copy
+    + From<u8>
+    + ops::Add<Output = Idx>
+    + ops::Sub<Output = Idx>,
+{
+  type Item = Idx;
+  fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+    let current = self.next?;
+    self.next = match current.partial_cmp(&self.end) {
+      Some(Ordering::Less) => Some(current + 1.into()),
+      Some(Ordering::Greater) => Some(current - 1.into()),
+      _ => None,
+    };
+    Some(current)
+  }
+}
+
+pub fn range(start: u32, end: u32) -> AnyDirRange<u32> {
+  AnyDirRange {
+    next: Some(start),
+    end,
+  }
+}
@@ -1,27 +1,43 @@
-use std::io;
-use std::io::BufRead;
+use crate::parse::Parser;
+use crate::{ParseResult, Result, Solution};
 
-type FishMap = [usize; 9];
+pub type FishMap = [usize; 9];
 
-pub fn read_fish(mut stdin: io::StdinLock) -> io::Result<FishMap> {
-  let mut map = [0; 9];
-  let mut line = String::new();
-  stdin.read_line(&mut line)?;
-
-  line
-    .trim()
-    .split(',')
-    .map(parse_num)
-    .try_for_each(|x| x.map(|n| map[n] += 1))?;
-  Ok(map)
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+  type Input = FishMap;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  fn parse(input: &str) -> Result<FishMap> {
+    Ok(read_fish(input)?)
+  }
+
+  fn part1(&fish: &FishMap) -> Result<usize> {
+    Ok(count_fish(fish, 80))
+  }
 
-fn parse_num(x: &str) -> io::Result<usize> {
-  x.parse().map_err(|_| bad_input(x))
+  fn part2(&fish: &FishMap) -> Result<usize> {
+    Ok(count_fish(fish, 256))
+  }
 }
 
-fn bad_input(input: &str) -> io::Error {
-  io::Error::new(io::ErrorKind::InvalidInput, input)
+pub fn read_fish(input: &str) -> ParseResult<FishMap> {
+  let parser = Parser::new(input);
+  let mut map = [0; 9];
+
+  let timers = parser.separated(input.trim(), ",", |x| {
+    match parser.number(x, "a timer from 0 to 8")? {
+      n @ 0..=8 => Ok(n),
+      _ => Err(parser.error(x, "a timer from 0 to 8")),
+    }
+  })?;
+
+  for n in timers {
+    map[n] += 1;
+  }
+  Ok(map)
 }
 
 pub fn count_fish(mut fish: FishMap, mut days: usize) -> usize {
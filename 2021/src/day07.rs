@@ -0,0 +1,37 @@
+use crate::parse::Parser;
+use crate::{Result, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+  type Input = Vec<i32>;
+  type Answer1 = i32;
+  type Answer2 = i32;
+
+  fn parse(input: &str) -> Result<Vec<i32>> {
+    Ok(Parser::new(input).integers(input, "a position")?)
+  }
+
+  fn part1(crabs: &Vec<i32>) -> Result<i32> {
+    let mut crabs = crabs.clone();
+    let mid = crabs.len() / 2;
+    let (_, nth, _) = crabs.select_nth_unstable(mid);
+    let n = *nth;
+    Ok(crabs.iter().map(|x| (x - n).abs()).sum())
+  }
+
+  fn part2(crabs: &Vec<i32>) -> Result<i32> {
+    // The cheapest position is within 0.5 of the average, so it is either
+    // the rounded down average or the next position.
+    let n = crabs.len() as i32;
+    let avg: i32 = crabs.iter().sum::<i32>() / n;
+    let fuel = |position: i32| -> i32 {
+      crabs.iter().map(|x| cost((x - position).abs())).sum()
+    };
+    Ok(fuel(avg).min(fuel(avg + 1)))
+  }
+}
+
+pub fn cost(distance: i32) -> i32 {
+  distance * (distance + 1) / 2
+}
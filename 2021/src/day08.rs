@@ -0,0 +1,249 @@
+use crate::parse::Parser;
+use crate::{AocError, ParseResult, Result, Solution};
+use partition::partition;
+
+use std::cmp::Ordering;
+use std::ops::BitOr;
+use std::ops::Range;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+  type Input = Vec<Display>;
+  type Answer1 = usize;
+  type Answer2 = u32;
+
+  fn parse(input: &str) -> Result<Vec<Display>> {
+    let parser = Parser::new(input);
+    Ok(
+      parser
+        .lines()
+        .map(|line| Display::parse(&parser, line))
+        .collect::<ParseResult<_>>()?,
+    )
+  }
+
+  fn part1(displays: &Vec<Display>) -> Result<usize> {
+    let mut counts = [0usize; 10];
+    for digits in solve_displays(displays)? {
+      for digit in digits {
+        counts[digit as usize] += 1;
+      }
+    }
+
+    Ok(counts[1] + counts[4] + counts[7] + counts[8])
+  }
+
+  fn part2(displays: &Vec<Display>) -> Result<u32> {
+    Ok(
+      solve_displays(displays)?
+        .iter()
+        .map(|[m, c, x, i]| m * 1000 + c * 100 + x * 10 + i)
+        .sum(),
+    )
+  }
+}
+
+fn solve_displays(displays: &[Display]) -> Result<Vec<[u32; 4]>> {
+  displays.iter().cloned().map(solve_display).collect()
+}
+
+pub fn solve_display(mut display: Display) -> Result<[u32; 4]> {
+  let mut missing = segment::ALL_SEGMENTS;
+  let mut candidates = [0; 7];
+  display.digits.sort_by(cmp_display_ranges);
+  let mut digits_iter = display.digits.iter();
+
+  let mut next = || next_digit(&mut digits_iter, &display.line);
+
+  let one = next()?;
+  missing &= !one;
+  candidates[2] = one;
+  candidates[5] = one;
+
+  let seven = next()?;
+  missing &= !seven;
+  candidates[0] = seven & !one;
+
+  let four = next()?;
+  missing &= !four;
+  candidates[1] = four & !one;
+  candidates[3] = four & !one;
+
+  let check = |condition: bool, message: &str| {
+    if condition {
+      Ok(())
+    } else {
+      Err(AocError::NoAnswer(format!("{} {}", &display.line, message)))
+    }
+  };
+
+  let [two, three, _] = {
+    let mut two_three_five = [next()?, next()?, next()?];
+    partition(&mut two_three_five, |n| (n & four).count_ones() == 2); // 2 is  first
+    partition(&mut two_three_five[1..], |n| (n & one).count_ones() == 2); // 3 is next
+    two_three_five
+  };
+
+  check(
+    candidates[0] & two == candidates[0],
+    "two does not contain segment 0",
+  )?;
+  missing &= !two;
+  candidates[1] &= !two;
+  candidates[2] &= two & !candidates[0];
+  candidates[3] &= two & !(one | seven);
+  candidates[4] = two & !(one | seven | four);
+  candidates[5] &= !two;
+  candidates[6] = two & !(one | seven | four);
+
+  check(missing == 0, "not all segments seen")?;
+
+  candidates[4] &= !three;
+  candidates[5] &= three & !two;
+  candidates[6] &= three;
+
+  check(
+    candidates.iter().all(|c| c.count_ones() == 1),
+    "segments have more than one candidate",
+  )?;
+  check(
+    candidates.iter().fold(0, BitOr::bitor) == segment::ALL_SEGMENTS,
+    "not all segments have a unique value",
+  )?;
+
+  Ok(display.outputs.map(|o| {
+    let mut segments: u32 = 0;
+    for segment_char in display.line[o].chars() {
+      segments |= find_mapping(segment_char, &candidates);
+    }
+    digit::with_segments(segments)
+  }))
+}
+
+fn next_digit(
+  digits_iter: &mut std::slice::Iter<Range<usize>>,
+  line: &str,
+) -> Result<u32> {
+  match digits_iter.next() {
+    Some(range) => Ok(segment::parse(&line[range.clone()])),
+    None => Err(AocError::NoAnswer(format!("{} has too few digits", line))),
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Display {
+  line: String,
+  digits: [Range<usize>; 10],
+  outputs: [Range<usize>; 4],
+}
+
+mod segment {
+  use std::ops::BitOr;
+
+  pub const A: u32 = 1 << 0;
+  pub const B: u32 = 1 << 1;
+  pub const C: u32 = 1 << 2;
+  pub const D: u32 = 1 << 3;
+  pub const E: u32 = 1 << 4;
+  pub const F: u32 = 1 << 5;
+  pub const G: u32 = 1 << 6;
+
+  pub const ALL_SEGMENTS: u32 = 0b1111111;
+  pub fn parse(segments: &str) -> u32 {
+    segments
+      .chars()
+      .map(|c| match c {
+        'a'..='g' => 1 << (c as u32 - 'a' as u32),
+        _ => 0,
+      })
+      .reduce(BitOr::bitor)
+      .unwrap_or(0)
+  }
+}
+
+mod digit {
+  use super::segment::{A, B, C, D, E, F, G};
+
+  static DIGIT_SEGMENTS: [(u32, u32); 10] = [
+    (C | F, 1),
+    (A | C | D | E | G, 2),
+    (A | C | D | F | G, 3),
+    (B | C | D | F, 4),
+    (A | B | D | F | G, 5),
+    (A | B | D | E | F | G, 6),
+    (A | C | F, 7),
+    (A | B | C | D | E | F | G, 8),
+    (A | B | C | D | F | G, 9),
+    (A | B | C | E | F | G, 0),
+  ];
+
+  pub fn with_segments(segments: u32) -> u32 {
+    DIGIT_SEGMENTS
+      .iter()
+      .find(|(bits, _)| *bits == segments)
+      .map_or(u32::MAX, |(_, n)| *n)
+  }
+}
+impl Display {
+  /// Parses `line`, which has to be a slice of the parser's input.
+  pub fn parse(parser: &Parser, line: &str) -> ParseResult<Display> {
+    let (digits, outputs) = parser.split(line, "|", "`|`")?;
+
+    Ok(Self {
+      line: line.to_string(),
+      digits: display_segments(parser, line, digits, "a signal pattern")?,
+      outputs: display_segments(parser, line, outputs, "an output digit")?,
+    })
+  }
+}
+
+/// The ranges of the `N` segment patterns in `text` relative to `line`.
+fn display_segments<const N: usize>(
+  parser: &Parser,
+  line: &str,
+  text: &str,
+  expected: &str,
+) -> ParseResult<[Range<usize>; N]> {
+  let words = parser.words(text, [expected; N])?;
+  let mut result = words.map(|_| 0..0);
+  for (range, word) in result.iter_mut().zip(words) {
+    if word.is_empty() || !word.bytes().all(|b| matches!(b, b'a'..=b'g')) {
+      return Err(parser.error(word, "segments from `a` to `g`"));
+    }
+    let start = word.as_ptr() as usize - line.as_ptr() as usize;
+    *range = start..start + word.len();
+  }
+
+  Ok(result)
+}
+
+fn find_mapping(chr: char, mappings: &[u32; 7]) -> u32 {
+  let x = match chr {
+    'a'..='g' => 1u32 << (chr as u32 - 'a' as u32),
+    _ => return 0,
+  };
+
+  mappings
+    .iter()
+    .enumerate()
+    .find(|(_, &c)| c == x)
+    .map_or(0, |(i, _)| 1 << i)
+}
+
+fn cmp_display_ranges(x: &Range<usize>, y: &Range<usize>) -> Ordering {
+  let a = x.len();
+  let b = y.len();
+
+  if a == b {
+    Ordering::Equal
+  } else if b == 6 {
+    Ordering::Less
+  } else if a == 6 {
+    Ordering::Greater
+  } else if a < b {
+    Ordering::Less
+  } else {
+    Ordering::Greater
+  }
+}
@@ -0,0 +1,80 @@
+use crate::grid::{Coord, Grid};
+use crate::parse::Parser;
+use crate::{Result, Solution};
+use std::collections::HashSet;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+  type Input = HeightMap;
+  type Answer1 = u32;
+  type Answer2 = usize;
+
+  fn parse(input: &str) -> Result<HeightMap> {
+    Ok(Parser::new(input).digit_grid(input, 0..=9, "a height")?)
+  }
+
+  fn part1(height_map: &HeightMap) -> Result<u32> {
+    Ok(
+      height_map
+        .coords()
+        .filter(|&coord| is_low_point(coord, height_map))
+        .map(|coord| risk_level(height_map[coord]) as u32)
+        .sum(),
+    )
+  }
+
+  fn part2(height_map: &HeightMap) -> Result<usize> {
+    let mut three_largest = [0, 0, 0];
+    height_map
+      .coords()
+      .filter(|&c| is_low_point(c, height_map))
+      .map(|c| basin_size(c, height_map))
+      .for_each(|s| {
+        if s > three_largest[0] {
+          three_largest[2] = three_largest[1];
+          three_largest[1] = three_largest[0];
+          three_largest[0] = s;
+        } else if s > three_largest[1] {
+          three_largest[2] = three_largest[1];
+          three_largest[1] = s;
+        } else if s > three_largest[2] {
+          three_largest[2] = s;
+        }
+      });
+
+    Ok(three_largest.iter().product())
+  }
+}
+
+pub type HeightMap = Grid<u8>;
+
+pub fn is_low_point(coord: Coord, height_map: &HeightMap) -> bool {
+  let height = height_map[coord];
+  height_map
+    .neighbours4(coord)
+    .all(|neighbour| height_map[neighbour] > height)
+}
+
+pub fn basin_size(coord: Coord, height_map: &HeightMap) -> usize {
+  let mut seen: HashSet<_> = [coord].into_iter().collect();
+  let mut next = vec![coord];
+  let mut size = 0;
+
+  while !next.is_empty() {
+    size += 1;
+    let coord = next.pop().unwrap();
+
+    next.extend(
+      height_map
+        .neighbours4(coord)
+        .filter(|&c| seen.insert(c) && height_map[c] < 9),
+    );
+  }
+
+  size
+}
+
+pub fn risk_level(height: u8) -> u8 {
+  height + 1
+}
@@ -0,0 +1,114 @@
+use crate::{ParseError, Result, Solution};
+use lazy_static::lazy_static;
+
+lazy_static! {
+  static ref CURRUPT_SCORES: [i32; 128] = {
+    let mut scores = [0; 128];
+    scores[')' as usize] = 3;
+    scores[']' as usize] = 57;
+    scores['}' as usize] = 1197;
+    scores['>' as usize] = 25137;
+    scores
+  };
+  static ref MISSING_SCORES: [i64; 128] = {
+    let mut scores = [0; 128];
+    scores[')' as usize] = 1;
+    scores[']' as usize] = 2;
+    scores['}' as usize] = 3;
+    scores['>' as usize] = 4;
+    scores
+  };
+  static ref CLOSING: [char; 128] = {
+    let mut closing = ['\0'; 128];
+    closing['(' as usize] = ')';
+    closing['[' as usize] = ']';
+    closing['{' as usize] = '}';
+    closing['<' as usize] = '>';
+    closing
+  };
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input = Vec<String>;
+  type Answer1 = i32;
+  type Answer2 = i64;
+
+  fn parse(input: &str) -> Result<Vec<String>> {
+    let invalid = input
+      .char_indices()
+      .find(|(_, c)| !"()[]{}<>\r\n".contains(*c));
+    if let Some((i, c)) = invalid {
+      let token = &input[i..i + c.len_utf8()];
+      return Err(ParseError::at(input, token, "a delimiter").into());
+    }
+
+    Ok(input.lines().map(str::to_string).collect())
+  }
+
+  fn part1(lines: &Vec<String>) -> Result<i32> {
+    Ok(corrupt_score(lines))
+  }
+
+  fn part2(lines: &Vec<String>) -> Result<i64> {
+    Ok(missing_score(lines))
+  }
+}
+
+pub fn corrupt_score(lines: &[String]) -> i32 {
+  lines
+    .iter()
+    .filter_map(|line| corrupt_closing_delimiter(line))
+    .fold(0, |score, corrupt| CURRUPT_SCORES[corrupt as usize] + score)
+}
+
+pub fn corrupt_closing_delimiter(line: &str) -> Option<char> {
+  let mut expected = vec![];
+  for c in line.chars() {
+    match c {
+      '(' | '[' | '{' | '<' => expected.push(CLOSING[c as usize]),
+      ')' | ']' | '}' | '>' => {
+        if expected.last() == Some(&c) {
+          expected.pop();
+        } else {
+          return Some(c);
+        }
+      }
+      _ => (),
+    };
+  }
+  None
+}
+
+pub fn missing_score(lines: &[String]) -> i64 {
+  let mut line_scores = lines
+    .iter()
+    .filter_map(|line| missing_delimiters(line))
+    .map(|line| {
+      line.iter().rev().fold(0, |score, &delim| {
+        score * 5 + MISSING_SCORES[delim as usize]
+      })
+    })
+    .collect::<Vec<_>>();
+  let mid = line_scores.len() / 2;
+  *line_scores[..].select_nth_unstable(mid).1
+}
+
+pub fn missing_delimiters(line: &str) -> Option<Vec<char>> {
+  let mut expected = vec![];
+  for c in line.chars() {
+    match c {
+      '(' | '[' | '{' | '<' => expected.push(CLOSING[c as usize]),
+      ')' | ']' | '}' | '>' => {
+        if expected.last() == Some(&c) {
+          expected.pop();
+        } else {
+          return None;
+        }
+      }
+      _ => (),
+    };
+  }
+  Some(expected)
+}
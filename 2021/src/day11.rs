@@ -0,0 +1,331 @@
+use crate::grid::{Coord, Grid};
+use crate::parse::Parser;
+use crate::solution::{Flag, Flags};
+use crate::{AocError, Result, Solution};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+pub type Map = Grid<u8>;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+  type Input = Map;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  const FLAGS: &'static [Flag] = &[
+    Flag {
+      name: "--visualize",
+      value: None,
+      help: "Animate the steps up to the first synchronized flash",
+    },
+    Flag {
+      name: "--waves",
+      value: None,
+      help: "Also animate each wave of flashes within a step",
+    },
+    Flag {
+      name: "--delay",
+      value: Some("<ms>"),
+      help: "Time between frames, 100 ms by default",
+    },
+    Flag {
+      name: "--csv",
+      value: Some("<path>"),
+      help: "Write per step statistics as CSV to <path>, or stdout if -",
+    },
+    Flag {
+      name: "--max-steps",
+      value: Some("<n>"),
+      help: "Steps to look for a cycle for --csv, 10000 by default",
+    },
+  ];
+
+  fn parse(input: &str) -> Result<Map> {
+    Ok(Parser::new(input).digit_grid(input, 0..=9, "an energy level")?)
+  }
+
+  fn part1(map: &Map) -> Result<usize> {
+    let mut map = map.clone();
+    Ok((0..100).map(|_| run_step(&mut map)).sum())
+  }
+
+  fn part2(map: &Map) -> Result<usize> {
+    let mut map = map.clone();
+    let mut seen = HashSet::new();
+    let mut step = 0;
+    while !map.iter().all(|&cell| cell == 0) {
+      if !seen.insert(map.clone()) {
+        return Err(AocError::NoAnswer("never synchronizes".to_string()));
+      }
+      run_step(&mut map);
+      step += 1;
+    }
+    Ok(step)
+  }
+
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    if let Some(path) = flags.value("--csv") {
+      let max_steps = flags.parse("--max-steps")?.unwrap_or(10000);
+      let simulation = simulate(&Self::parse(input)?, max_steps);
+      if path == "-" {
+        write!(out, "{}", to_csv(&simulation.records))?;
+      } else {
+        fs::write(path, to_csv(&simulation.records))?;
+        writeln!(out, "{}", simulation)?;
+      }
+      return Ok(());
+    }
+    if !flags.has("--visualize") {
+      return Err(AocError::Unsupported(
+        "--waves and --delay need --visualize, --max-steps needs --csv"
+          .to_string(),
+      ));
+    }
+
+    let animation = Animation {
+      waves: flags.has("--waves"),
+      delay: Duration::from_millis(flags.parse("--delay")?.unwrap_or(100)),
+    };
+    animate(&Self::parse(input)?, &animation, out)?;
+    Ok(())
+  }
+}
+
+pub const FLASH_THRESHOLD: u8 = 10;
+
+pub fn run_step(map: &mut Map) -> usize {
+  run_step_with(map, |_, _| {})
+}
+
+/// Runs a step like `run_step`, calling `on_wave` for each wave of flashes:
+/// first the cells flashing from the step's own increase, then those
+/// flashing from the previous wave. Flashed cells reset to 0 after the last
+/// wave.
+pub fn run_step_with(
+  map: &mut Map,
+  mut on_wave: impl FnMut(&Map, &[Coord]),
+) -> usize {
+  let mut flashes: Vec<_> = map
+    .enumerate_mut()
+    .filter_map(|(coord, value)| {
+      *value += 1;
+      match value {
+        FLASH_THRESHOLD.. => Some(coord),
+        _ => None,
+      }
+    })
+    .collect();
+
+  let mut wave_start = 0;
+  while wave_start < flashes.len() {
+    let wave_end = flashes.len();
+    on_wave(map, &flashes[wave_start..wave_end]);
+
+    for i in wave_start..wave_end {
+      for neighbour in map.neighbours8(flashes[i]) {
+        let cell = &mut map[neighbour];
+        *cell += 1;
+        if *cell == FLASH_THRESHOLD {
+          flashes.push(neighbour);
+        }
+      }
+    }
+    wave_start = wave_end;
+  }
+
+  for &coord in &flashes {
+    map[coord] = 0;
+  }
+
+  flashes.len()
+}
+
+#[derive(Clone, Debug)]
+pub struct Animation {
+  /// Draw every wave of flashes, not just the end of each step.
+  pub waves: bool,
+  pub delay: Duration,
+}
+
+/// Draws `map` as ANSI terminal frames, step by step until every octopus
+/// flashes at once, and returns that step. None if the grid gets back to an
+/// earlier state first, so it never synchronizes.
+pub fn animate(
+  map: &Map,
+  animation: &Animation,
+  out: &mut dyn io::Write,
+) -> io::Result<Option<usize>> {
+  let mut map = map.clone();
+  let mut seen = HashSet::new();
+  let mut step = 0;
+
+  write!(out, "\x1b[2J")?;
+  draw_frame(out, &map, "step 0", animation.delay)?;
+
+  while !map.iter().all(|&cell| cell == 0) {
+    if !seen.insert(map.clone()) {
+      writeln!(
+        out,
+        "never synchronizes, step {} repeats an earlier one",
+        step
+      )?;
+      out.flush()?;
+      return Ok(None);
+    }
+    step += 1;
+    let mut wave = 0;
+    let mut result = Ok(());
+    let flashes = run_step_with(&mut map, |map, _| {
+      wave += 1;
+      if animation.waves && result.is_ok() {
+        let title = format!("step {}, wave {}", step, wave);
+        result = draw_frame(out, map, &title, animation.delay);
+      }
+    });
+    result?;
+
+    let title = format!("step {}, {} flashes in {} waves", step, flashes, wave);
+    draw_frame(out, &map, &title, animation.delay)?;
+  }
+
+  writeln!(out, "synchronized after {} steps", step)?;
+  out.flush()?;
+  Ok(Some(step))
+}
+
+fn draw_frame(
+  out: &mut dyn io::Write,
+  map: &Map,
+  title: &str,
+  delay: Duration,
+) -> io::Result<()> {
+  writeln!(out, "\x1b[H{}\x1b[K", title)?;
+  for row in map.rows() {
+    for &energy in row {
+      match energy {
+        0 => write!(out, "\x1b[1;97m0")?,
+        FLASH_THRESHOLD.. => write!(out, "\x1b[1;93m*")?,
+        _ => write!(out, "\x1b[0;38;5;{}m{}", 232 + 2 * energy, energy)?,
+      }
+    }
+    writeln!(out, "\x1b[0m")?;
+  }
+  out.flush()?;
+  thread::sleep(delay);
+  Ok(())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StepRecord {
+  /// 1-based, the state after this many steps.
+  pub step: usize,
+  pub flashes: usize,
+  /// The number of waves of flashes, see `run_step_with`.
+  pub cascade_depth: usize,
+  /// The cells that flashed, in the order they did.
+  pub flashed: Vec<Coord>,
+}
+
+/// Steps after which the grid repeats itself: the state after `start`
+/// steps comes back every `period` steps.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+  pub start: usize,
+  pub period: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Simulation {
+  pub cells: usize,
+  pub records: Vec<StepRecord>,
+  /// None if no state repeated within the simulated steps.
+  pub cycle: Option<Cycle>,
+}
+
+impl Simulation {
+  /// The first step in which every octopus flashed.
+  pub fn synchronized(&self) -> Option<usize> {
+    self
+      .records
+      .iter()
+      .find(|record| record.flashes == self.cells)
+      .map(|record| record.step)
+  }
+}
+
+/// Runs `map` until its state repeats, or for `max_steps` steps.
+pub fn simulate(map: &Map, max_steps: usize) -> Simulation {
+  let mut map = map.clone();
+  let mut seen = HashMap::from([(map.clone(), 0)]);
+  let mut records = vec![];
+  let mut cycle = None;
+
+  for step in 1..=max_steps {
+    let mut flashed = vec![];
+    let mut cascade_depth = 0;
+    let flashes = run_step_with(&mut map, |_, wave| {
+      cascade_depth += 1;
+      flashed.extend_from_slice(wave);
+    });
+    records.push(StepRecord {
+      step,
+      flashes,
+      cascade_depth,
+      flashed,
+    });
+
+    if let Some(start) = seen.insert(map.clone(), step) {
+      cycle = Some(Cycle {
+        start,
+        period: step - start,
+      });
+      break;
+    }
+  }
+
+  Simulation {
+    cells: map.width() * map.height(),
+    records,
+    cycle,
+  }
+}
+
+/// One line per step, flashed cells as space separated `row:col` pairs.
+pub fn to_csv(records: &[StepRecord]) -> String {
+  let mut csv = String::from("step,flashes,cascade_depth,flashed\n");
+  for record in records {
+    write!(
+      csv,
+      "{},{},{},",
+      record.step, record.flashes, record.cascade_depth
+    )
+    .unwrap();
+    for (i, (row, col)) in record.flashed.iter().enumerate() {
+      let separator = if i == 0 { "" } else { " " };
+      write!(csv, "{}{}:{}", separator, row, col).unwrap();
+    }
+    csv.push('\n');
+  }
+  csv
+}
+
+impl fmt::Display for Simulation {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    write!(formatter, "{} steps", self.records.len())?;
+    if let Some(step) = self.synchronized() {
+      write!(formatter, ", first synchronized flash in step {}", step)?;
+    }
+    match self.cycle {
+      Some(Cycle { start, period }) => {
+        write!(formatter, ", cycle of {} steps from step {}", period, start)
+      }
+      None => write!(formatter, ", no cycle"),
+    }
+  }
+}
@@ -0,0 +1,599 @@
+use crate::parse::Parser;
+use crate::solution::{Flag, Flags};
+use crate::{AocError, ParseResult, Result, Solution};
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+  type Input = CaveSystem;
+  type Answer1 = u64;
+  type Answer2 = u64;
+
+  const FLAGS: &'static [Flag] = &[
+    Flag {
+      name: "--paths",
+      value: None,
+      help: "Print every path, one per line in sorted order",
+    },
+    Flag {
+      name: "--count",
+      value: None,
+      help: "Print the number of paths, counted without listing them",
+    },
+    Flag {
+      name: "--repeats",
+      value: Some("<n>"),
+      help: "Small caves a path may visit twice, 0 by default",
+    },
+    Flag {
+      name: "--limit",
+      value: Some("<cave=n,...>"),
+      help: "Visits allowed to single caves",
+    },
+    Flag {
+      name: "--forbid",
+      value: Some("<cave,...>"),
+      help: "Caves a path may not enter",
+    },
+    Flag {
+      name: "--end",
+      value: Some("<cave>"),
+      help: "Cave the paths end at, end by default",
+    },
+    Flag {
+      name: "--through",
+      value: Some("<cave>"),
+      help: "Only print or count paths visiting <cave>",
+    },
+  ];
+
+  fn parse(input: &str) -> Result<CaveSystem> {
+    let parser = Parser::new(input);
+    let mut cave_system = CaveSystem::new();
+
+    for line in parser.lines() {
+      cave_system.add_connection(CaveConnection::parse(&parser, line)?)
+    }
+
+    Ok(cave_system)
+  }
+
+  fn part1(cave_system: &CaveSystem) -> Result<u64> {
+    CaveGraph::new(cave_system)?.count_paths(&VisitPolicy::new(0))
+  }
+
+  fn part2(cave_system: &CaveSystem) -> Result<u64> {
+    CaveGraph::new(cave_system)?.count_paths(&VisitPolicy::new(1))
+  }
+
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    let cave_system = Self::parse(input)?;
+    let policy = visit_policy(flags)?;
+    let through = flags.value("--through").map(Cave::new);
+    if flags.has("--count") {
+      let graph = CaveGraph::new(&cave_system)?;
+      let count = match &through {
+        Some(cave) => graph.count_paths_through(&policy, cave)?,
+        None => graph.count_paths(&policy)?,
+      };
+      writeln!(out, "{}", count)?;
+      return Ok(());
+    }
+    if !flags.has("--paths") {
+      return Err(AocError::Unsupported(
+        "Pick --paths or --count for the other flags to apply to".to_string(),
+      ));
+    }
+
+    for path in paths(&cave_system, &policy)? {
+      if through.as_ref().is_none_or(|cave| path.visits(cave)) {
+        writeln!(out, "{}", path)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+fn visit_policy(flags: &Flags) -> Result<VisitPolicy> {
+  let mut policy = VisitPolicy::new(flags.parse("--repeats")?.unwrap_or(0));
+  let names = |flag| flags.value(flag).into_iter().flat_map(|v| v.split(','));
+
+  for limit in names("--limit") {
+    let (cave, visits) = limit
+      .split_once('=')
+      .and_then(|(cave, visits)| Some((cave, visits.parse().ok()?)))
+      .ok_or_else(|| {
+        AocError::Unsupported(format!("Invalid limit for --limit: {}", limit))
+      })?;
+    policy.limits.insert(Cave::new(cave), visits);
+  }
+  for cave in names("--forbid") {
+    policy.limits.insert(Cave::new(cave), 0);
+  }
+  if let Some(end) = flags.value("--end") {
+    policy.end = Cave::new(end);
+  }
+  Ok(policy)
+}
+
+/// Which caves a path may enter how often, and where it ends.
+#[derive(Clone, Debug)]
+pub struct VisitPolicy {
+  /// The number of distinct small caves a path may visit twice.
+  pub twice: u32,
+  /// Visits allowed to single caves, big or small, 0 forbidding a cave.
+  /// Caves with a limit are never among the ones visited twice.
+  pub limits: HashMap<Cave, u32>,
+  pub end: Cave,
+}
+
+/// How often a path may enter a cave.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Limit {
+  Unlimited,
+  AtMost(u32),
+  /// Once, or twice if fewer than `VisitPolicy::twice` small caves have
+  /// been visited twice.
+  OnceOrTwice,
+}
+
+impl VisitPolicy {
+  /// The puzzle's rules: big caves any number of times, small caves once
+  /// except for `twice` of them, from `start` to `end`.
+  pub fn new(twice: u32) -> Self {
+    Self {
+      twice,
+      limits: HashMap::new(),
+      end: Cave::end(),
+    }
+  }
+
+  pub fn limit(&self, cave: &Cave) -> Limit {
+    if let Some(&visits) = self.limits.get(cave) {
+      Limit::AtMost(visits)
+    } else if *cave == Cave::start() {
+      Limit::AtMost(1)
+    } else if cave.is_lowercase() {
+      Limit::OnceOrTwice
+    } else {
+      Limit::Unlimited
+    }
+  }
+}
+
+/// Fails if two connected caves may both be entered any number of times, so
+/// paths could go back and forth between them forever. Paths stop at the
+/// end cave, so it never counts.
+fn check_paths_end<'a>(
+  policy: &VisitPolicy,
+  connections: impl IntoIterator<Item = (&'a Cave, &'a Cave)>,
+) -> Result<()> {
+  let unlimited =
+    |cave: &Cave| *cave != policy.end && policy.limit(cave) == Limit::Unlimited;
+  match connections
+    .into_iter()
+    .filter(|(from, to)| unlimited(from) && unlimited(to))
+    .min()
+  {
+    Some((from, to)) => Err(AocError::NoAnswer(format!(
+      "caves {} and {} are connected, so paths never end",
+      from.name(),
+      to.name()
+    ))),
+    None => Ok(()),
+  }
+}
+
+/// Counts the paths by listing them, see `CaveGraph::count_paths` for a
+/// faster way.
+pub fn find_paths(
+  cave_system: &CaveSystem,
+  policy: &VisitPolicy,
+) -> Result<usize> {
+  Ok(paths(cave_system, policy)?.count())
+}
+
+/// The complete paths through `cave_system`, see `Paths`. Fails like
+/// `CaveGraph::count_paths` if paths never end.
+pub fn paths<'a>(
+  cave_system: &'a CaveSystem,
+  policy: &'a VisitPolicy,
+) -> Result<Paths<'a>> {
+  check_paths_end(policy, cave_system.connections())?;
+  let open_paths = match policy.limit(&Cave::start()) {
+    Limit::AtMost(0) => vec![],
+    _ => vec![CaveSystemPath::start(policy)],
+  };
+  Ok(Paths {
+    cave_system,
+    policy,
+    open_paths,
+  })
+}
+
+/// Complete paths in sorted order of their caves. They are found depth
+/// first, so only the paths still being extended are held in memory.
+pub struct Paths<'a> {
+  cave_system: &'a CaveSystem,
+  policy: &'a VisitPolicy,
+  /// A stack with the next path to extend on top.
+  open_paths: Vec<CaveSystemPath>,
+}
+
+impl Iterator for Paths<'_> {
+  type Item = CaveSystemPath;
+
+  fn next(&mut self) -> Option<CaveSystemPath> {
+    while let Some(path) = self.open_paths.pop() {
+      if path.is_complete(self.policy) {
+        return Some(path);
+      }
+      let len = self.open_paths.len();
+      self
+        .open_paths
+        .extend(path.step(self.cave_system, self.policy));
+      self.open_paths[len..].reverse();
+    }
+    None
+  }
+}
+
+/// A cave system with caves numbered for counting paths quickly. Small
+/// caves come first, so a set of them fits a bitmask.
+#[derive(Clone, Debug)]
+pub struct CaveGraph {
+  caves: Vec<Cave>,
+  ids: HashMap<Cave, usize>,
+  small_caves: usize,
+  connections: Vec<Vec<usize>>,
+}
+
+/// A `Limit` for a numbered cave.
+#[derive(Clone, Copy, Debug)]
+enum Rule {
+  Unlimited,
+  Forbidden,
+  /// Tracked by the cave's bit in `Visit::visited`.
+  Once,
+  /// Tracked by its bits in `Visit::visited` and `Visit::visited_twice`.
+  OnceOrTwice,
+  /// Tracked by a counter in `Visit::counts`.
+  AtMost(usize, u32),
+}
+
+/// Everything the number of paths on from a cave depends on.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Visit {
+  cave: usize,
+  visited: u64,
+  visited_twice: u64,
+  twice_left: u32,
+  counts: Vec<u32>,
+}
+
+impl CaveGraph {
+  pub const MAX_SMALL_CAVES: usize = u64::BITS as usize;
+
+  /// Fails for systems with too many small caves.
+  pub fn new(cave_system: &CaveSystem) -> Result<Self> {
+    let mut caves: Vec<Cave> =
+      cave_system.connections.keys().cloned().collect();
+    caves.sort_by_key(|cave| (!cave.is_lowercase(), cave.clone()));
+    let small_caves = caves.iter().filter(|cave| cave.is_lowercase()).count();
+    if small_caves > Self::MAX_SMALL_CAVES {
+      return Err(AocError::NoAnswer(format!(
+        "{} small caves, at most {} can be counted",
+        small_caves,
+        Self::MAX_SMALL_CAVES
+      )));
+    }
+
+    let ids: HashMap<Cave, usize> = caves.iter().cloned().zip(0..).collect();
+    let connections = caves
+      .iter()
+      .map(|cave| cave_system.connections_from(cave).map(|c| ids[c]).collect())
+      .collect();
+
+    Ok(Self {
+      caves,
+      ids,
+      small_caves,
+      connections,
+    })
+  }
+
+  pub fn cave(&self, id: usize) -> &Cave {
+    &self.caves[id]
+  }
+
+  pub fn id(&self, cave: &Cave) -> Option<usize> {
+    self.ids.get(cave).copied()
+  }
+
+  /// The number of paths following `policy`, like `find_paths`. Fails
+  /// without a start or end, if the count overflows or if two connected
+  /// caves can be visited any number of times, so paths never end.
+  pub fn count_paths(&self, policy: &VisitPolicy) -> Result<u64> {
+    let find = |cave: &Cave| {
+      self
+        .id(cave)
+        .ok_or_else(|| AocError::NoAnswer(format!("no {} cave", cave.name())))
+    };
+    let start = find(&Cave::start())?;
+    let end = find(&policy.end)?;
+
+    let mut counters = 0;
+    let rules: Vec<Rule> = (0..self.caves.len())
+      .map(|id| match policy.limit(&self.caves[id]) {
+        Limit::Unlimited => Rule::Unlimited,
+        Limit::AtMost(0) => Rule::Forbidden,
+        Limit::AtMost(1) if id < self.small_caves => Rule::Once,
+        Limit::AtMost(visits) => {
+          counters += 1;
+          Rule::AtMost(counters - 1, visits)
+        }
+        Limit::OnceOrTwice => Rule::OnceOrTwice,
+      })
+      .collect();
+
+    check_paths_end(policy, self.connection_pairs())?;
+
+    let visit = Visit {
+      cave: start,
+      visited: 0,
+      visited_twice: 0,
+      twice_left: policy.twice,
+      counts: vec![0; counters],
+    };
+    let count = match self.enter(&rules, &visit, start) {
+      Some(visit) => self.count_from(&rules, end, visit, &mut HashMap::new()),
+      None => Some(0),
+    };
+    count
+      .ok_or_else(|| AocError::NoAnswer("too many paths to count".to_string()))
+  }
+
+  /// The number of paths following `policy` that visit `cave`, which are
+  /// the ones missing once `cave` is forbidden.
+  pub fn count_paths_through(
+    &self,
+    policy: &VisitPolicy,
+    cave: &Cave,
+  ) -> Result<u64> {
+    let mut avoiding = policy.clone();
+    avoiding.limits.insert(cave.clone(), 0);
+    Ok(self.count_paths(policy)? - self.count_paths(&avoiding)?)
+  }
+
+  fn connection_pairs(&self) -> impl Iterator<Item = (&Cave, &Cave)> {
+    self
+      .connections
+      .iter()
+      .enumerate()
+      .flat_map(move |(from, to)| {
+        to.iter()
+          .map(move |&to| (&self.caves[from], &self.caves[to]))
+      })
+  }
+
+  /// Paths from the visited cave to `end`, memoized on the visit.
+  fn count_from(
+    &self,
+    rules: &[Rule],
+    end: usize,
+    visit: Visit,
+    memo: &mut HashMap<Visit, u64>,
+  ) -> Option<u64> {
+    if visit.cave == end {
+      return Some(1);
+    }
+    if let Some(&count) = memo.get(&visit) {
+      return Some(count);
+    }
+
+    let mut count = 0u64;
+    for &next in &self.connections[visit.cave] {
+      if let Some(next) = self.enter(rules, &visit, next) {
+        count = count.checked_add(self.count_from(rules, end, next, memo)?)?;
+      }
+    }
+
+    memo.insert(visit, count);
+    Some(count)
+  }
+
+  /// The visit after moving on to `cave`, None if the rules forbid it.
+  fn enter(&self, rules: &[Rule], visit: &Visit, cave: usize) -> Option<Visit> {
+    let mut next = Visit {
+      cave,
+      ..visit.clone()
+    };
+    let bit = 1u64.checked_shl(cave as u32).unwrap_or(0);
+    match rules[cave] {
+      Rule::Unlimited => {}
+      Rule::Forbidden => return None,
+      Rule::Once if visit.visited & bit != 0 => return None,
+      Rule::Once => next.visited |= bit,
+      Rule::OnceOrTwice if visit.visited & bit == 0 => next.visited |= bit,
+      Rule::OnceOrTwice if visit.visited_twice & bit != 0 => return None,
+      Rule::OnceOrTwice => {
+        next.visited_twice |= bit;
+        next.twice_left = visit.twice_left.checked_sub(1)?;
+      }
+      Rule::AtMost(counter, visits) if visit.counts[counter] >= visits => {
+        return None
+      }
+      Rule::AtMost(counter, _) => next.counts[counter] += 1,
+    }
+    Some(next)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cave {
+  name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CaveConnection(pub Cave, pub Cave);
+
+#[derive(Clone, Debug, Default)]
+pub struct CaveSystem {
+  connections: HashMap<Cave, BTreeSet<Cave>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CaveSystemPath {
+  /// The number of times the path entered each cave.
+  visits: HashMap<Cave, u32>,
+  twice_left: u32,
+  /// The caves in the order the path visits them, starting with `start`.
+  caves: Vec<Cave>,
+}
+
+impl Cave {
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+    }
+  }
+  pub fn end() -> Self {
+    Self::new("end")
+  }
+  pub fn start() -> Self {
+    Self::new("start")
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn is_lowercase(&self) -> bool {
+    self
+      .name
+      .chars()
+      .next()
+      .map(char::is_lowercase)
+      .unwrap_or(false)
+  }
+}
+
+impl CaveSystem {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn get_or_insert(&mut self, cave: Cave) -> &mut BTreeSet<Cave> {
+    self.connections.entry(cave).or_default()
+  }
+
+  pub fn add_connection(&mut self, connection: CaveConnection) {
+    let a = self.get_or_insert(connection.0.clone());
+    if !a.contains(&connection.1) {
+      a.insert(connection.1.clone());
+    }
+
+    self.get_or_insert(connection.1).insert(connection.0);
+  }
+
+  /// Every connection, once from each of its caves.
+  pub fn connections(&self) -> impl Iterator<Item = (&Cave, &Cave)> {
+    self
+      .connections
+      .iter()
+      .flat_map(|(from, to)| to.iter().map(move |to| (from, to)))
+  }
+
+  /// The caves connected to `cave`, in sorted order.
+  pub fn connections_from(&self, cave: &Cave) -> impl Iterator<Item = &Cave> {
+    self.connections.get(cave).into_iter().flatten()
+  }
+}
+
+impl CaveSystemPath {
+  pub fn start(policy: &VisitPolicy) -> Self {
+    Self {
+      visits: HashMap::from([(Cave::start(), 1)]),
+      twice_left: policy.twice,
+      caves: vec![Cave::start()],
+    }
+  }
+
+  pub fn caves(&self) -> &[Cave] {
+    &self.caves
+  }
+
+  pub fn last(&self) -> &Cave {
+    self.caves.last().unwrap()
+  }
+
+  pub fn visits(&self, cave: &Cave) -> bool {
+    self.visits.contains_key(cave)
+  }
+
+  pub fn is_complete(&self, policy: &VisitPolicy) -> bool {
+    *self.last() == policy.end
+  }
+
+  pub fn step<'a>(
+    &'a self,
+    cave_system: &'a CaveSystem,
+    policy: &'a VisitPolicy,
+  ) -> impl Iterator<Item = CaveSystemPath> + 'a {
+    cave_system
+      .connections_from(self.last())
+      .filter_map(|cave| {
+        let visits = self.visits.get(cave).copied().unwrap_or(0);
+        let mut twice_left = self.twice_left;
+        let allowed = match policy.limit(cave) {
+          Limit::Unlimited => true,
+          Limit::AtMost(limit) => visits < limit,
+          Limit::OnceOrTwice if visits == 0 => true,
+          Limit::OnceOrTwice if visits == 1 && twice_left > 0 => {
+            twice_left -= 1;
+            true
+          }
+          Limit::OnceOrTwice => false,
+        };
+        if !allowed {
+          return None;
+        }
+
+        let mut path = self.clone();
+        path.twice_left = twice_left;
+        *path.visits.entry(cave.clone()).or_default() += 1;
+        path.caves.push(cave.clone());
+        Some(path)
+      })
+  }
+}
+
+/// The caves separated by commas, e.g. `start,A,b,A,end`.
+impl fmt::Display for CaveSystemPath {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    for (i, cave) in self.caves.iter().enumerate() {
+      let separator = if i == 0 { "" } else { "," };
+      write!(formatter, "{}{}", separator, cave.name())?;
+    }
+    Ok(())
+  }
+}
+
+impl CaveConnection {
+  /// Parses `line`, which has to be a slice of the parser's input.
+  pub fn parse(parser: &Parser, line: &str) -> ParseResult<Self> {
+    let (from, to) = parser.edge(line)?;
+
+    for cave in [from, to] {
+      if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(parser.error(cave, "a cave name"));
+      }
+    }
+
+    Ok(CaveConnection(Cave::new(from), Cave::new(to)))
+  }
+}
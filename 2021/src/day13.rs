@@ -0,0 +1,546 @@
+use crate::bitset::BitGrid;
+use crate::grid::Grid;
+use crate::ocr;
+use crate::parse::Parser;
+use crate::solution::{Flag, Flags};
+use crate::{AocError, ParseError, ParseResult, Result, Solution};
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::Path;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+  type Input = DotsAndFolds;
+  type Answer1 = usize;
+  type Answer2 = String;
+
+  const FLAGS: &'static [Flag] = &[
+    Flag {
+      name: "--replay",
+      value: None,
+      help: "Print the sheet before and after every fold",
+    },
+    Flag {
+      name: "--pbm",
+      value: Some("<dir>"),
+      help: "Write the sheet after every fold as PBM images to <dir>",
+    },
+    Flag {
+      name: "--strict",
+      value: None,
+      help: "Reject dots that end up on a fold line rather than dropping them",
+    },
+  ];
+
+  fn parse(input: &str) -> Result<DotsAndFolds> {
+    Ok(input.try_into()?)
+  }
+
+  fn part1(DotsAndFolds { dots, folds }: &DotsAndFolds) -> Result<usize> {
+    let fold = *folds
+      .front()
+      .ok_or_else(|| AocError::NoAnswer("no folds".to_string()))?;
+    let sheet = Sheet::new(dots.iter().copied());
+    Ok(sheet.fold(fold, OnFoldLine::Drop)?.len())
+  }
+
+  fn part2(DotsAndFolds { dots, folds }: &DotsAndFolds) -> Result<String> {
+    let mut sheet = Sheet::new(dots.iter().copied());
+    for &fold in folds {
+      sheet = sheet.fold(fold, OnFoldLine::Drop)?;
+    }
+
+    ocr::read(&sheet.to_grid()?)
+  }
+
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    let input = if flags.has("--strict") {
+      DotsAndFolds::parse_strict(input)?
+    } else {
+      Self::parse(input)?
+    };
+    let dir = flags.value("--pbm").map(Path::new);
+    if let Some(dir) = dir {
+      fs::create_dir_all(dir)?;
+    }
+
+    for (i, stage) in replay(&input).enumerate() {
+      let stage = stage?;
+      writeln!(out, "{}", stage)?;
+      if flags.has("--replay") {
+        writeln!(out, "{}", sheet_text(&stage.dots.to_grid()?))?;
+      }
+      if let Some(dir) = dir {
+        let image = render_pbm(&stage.dots.to_grid()?);
+        fs::write(dir.join(format!("fold-{:02}.pbm", i)), image)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Dot {
+  pub x: u32,
+  pub y: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Fold {
+  X(u32),
+  Y(u32),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DotsAndFolds {
+  pub dots: Vec<Dot>,
+  pub folds: VecDeque<Fold>,
+}
+
+impl TryFrom<&str> for DotsAndFolds {
+  type Error = ParseError;
+
+  fn try_from(input: &str) -> ParseResult<DotsAndFolds> {
+    let parser = Parser::new(input);
+    let [dots, folds] = parser.split_sections(["dots", "folds"])?;
+
+    Ok(DotsAndFolds {
+      dots: dots
+        .lines()
+        .map(|line| Dot::parse(&parser, line))
+        .collect::<ParseResult<_>>()?,
+      folds: folds
+        .lines()
+        .map(|line| Fold::parse(&parser, line))
+        .collect::<ParseResult<_>>()?,
+    })
+  }
+}
+
+impl DotsAndFolds {
+  /// Parses `input` like `Day13::parse`, failing at the first dot that a fold
+  /// puts on its fold line.
+  pub fn parse_strict(input: &str) -> ParseResult<DotsAndFolds> {
+    let parsed = DotsAndFolds::try_from(input)?;
+    let parser = Parser::new(input);
+    let [dot_lines, fold_lines] = parser.split_sections(["dots", "folds"])?;
+
+    // Folding the list keeps every dot at the index of its input line.
+    let mut dots = parsed.dots.clone();
+    for (&fold, fold_line) in parsed.folds.iter().zip(fold_lines.lines()) {
+      let on_line = |dot: &Dot| fold.position(dot) == fold.line();
+      if let Some(i) = dots.iter().position(on_line) {
+        let dot_line = dot_lines.lines().nth(i).unwrap_or_default();
+        let expected = format!(
+          "a dot off the line of {} (line {})",
+          fold,
+          parser.error(fold_line, "").line
+        );
+        return Err(parser.error(dot_line.trim_end(), expected));
+      }
+      let shift = fold.shift(dots.iter().copied());
+      for dot in &mut dots {
+        *dot = fold.reflect(*dot, shift);
+      }
+    }
+    Ok(parsed)
+  }
+}
+
+impl Dot {
+  /// Parses `line`, which has to be a slice of the parser's input.
+  pub fn parse(parser: &Parser, line: &str) -> ParseResult<Dot> {
+    let (x, y) = parser.split(line.trim_end(), ",", "a dot `x,y`")?;
+    Ok(Dot {
+      x: parser.number(x, "a coordinate")?,
+      y: parser.number(y, "a coordinate")?,
+    })
+  }
+}
+
+impl std::fmt::Display for Dot {
+  fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fmt.write_fmt(format_args!("{},{}", self.x, self.y))
+  }
+}
+
+impl Ord for Dot {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    match self.y.cmp(&other.y) {
+      std::cmp::Ordering::Equal => self.x.cmp(&other.x),
+      ordering => ordering,
+    }
+  }
+}
+
+impl PartialOrd for Dot {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl fmt::Display for Fold {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Fold::X(x) => write!(formatter, "fold along x={}", x),
+      Fold::Y(y) => write!(formatter, "fold along y={}", y),
+    }
+  }
+}
+
+impl Fold {
+  pub fn line(self) -> u32 {
+    match self {
+      Fold::X(line) | Fold::Y(line) => line,
+    }
+  }
+
+  /// The coordinate of `dot` across the fold line.
+  pub fn position(self, dot: &Dot) -> u32 {
+    match self {
+      Fold::X(_) => dot.x,
+      Fold::Y(_) => dot.y,
+    }
+  }
+
+  /// How far `dots` have to move for none to fold past 0.
+  fn shift(self, dots: impl Iterator<Item = Dot>) -> u32 {
+    let far = dots.map(|dot| self.position(&dot)).max().unwrap_or(0);
+    far.saturating_sub(2 * self.line())
+  }
+
+  /// `dot` after folding, moved by `shift` across the fold line.
+  fn reflect(self, mut dot: Dot, shift: u32) -> Dot {
+    let line = self.line();
+    let position = match self {
+      Fold::X(_) => &mut dot.x,
+      Fold::Y(_) => &mut dot.y,
+    };
+    if *position > line {
+      *position = line + shift - (*position - line);
+    } else {
+      *position += shift;
+    }
+    dot
+  }
+
+  /// Parses `line`, which has to be a slice of the parser's input.
+  pub fn parse(parser: &Parser, line: &str) -> ParseResult<Fold> {
+    let fold = parser.prefixed(line.trim_end(), "fold along ")?;
+    let (axis, n) = parser.split(fold, "=", "`x=n` or `y=n`")?;
+    let n = parser.number(n, "a fold position")?;
+    match axis {
+      "x" => Ok(Fold::X(n)),
+      "y" => Ok(Fold::Y(n)),
+      _ => Err(parser.error(axis, "`x` or `y`")),
+    }
+  }
+}
+
+/// What to do with dots lying on a fold line, which the puzzle never has.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OnFoldLine {
+  Drop,
+  Reject,
+}
+
+pub fn apply_fold(fold: Fold, dots: &mut Vec<Dot>) -> Result<()> {
+  apply_fold_with(fold, dots, OnFoldLine::Drop)
+}
+
+/// Folds the part past the fold line over the other part. When that part is
+/// the larger one, the sheet grows towards 0 and every dot shifts to keep
+/// coordinates from going negative.
+pub fn apply_fold_with(
+  fold: Fold,
+  dots: &mut Vec<Dot>,
+  on_fold_line: OnFoldLine,
+) -> Result<()> {
+  let line = fold.line();
+  match on_fold_line {
+    OnFoldLine::Drop => dots.retain(|dot| fold.position(dot) != line),
+    OnFoldLine::Reject => {
+      if let Some(dot) = dots.iter().find(|dot| fold.position(dot) == line) {
+        return Err(on_fold_line_error(*dot, fold));
+      }
+    }
+  }
+
+  let shift = fold.shift(dots.iter().copied());
+  for dot in dots {
+    *dot = fold.reflect(*dot, shift);
+  }
+
+  Ok(())
+}
+
+/// Distinct dots, kept in a bitmap when that is not much larger than a hash
+/// set, so folds never pile up duplicates.
+#[derive(Clone, Debug)]
+pub enum Sheet {
+  Sparse(HashSet<Dot>),
+  /// Rows of the bitmap are `y` coordinates, columns `x` coordinates.
+  Dense(BitGrid),
+}
+
+impl Sheet {
+  /// The most bits per dot a bitmap may take.
+  pub const DENSE_BITS_PER_DOT: usize = 64;
+  /// The most cells `to_grid` draws.
+  pub const MAX_GRID_CELLS: usize = 1 << 24;
+
+  pub fn new(dots: impl IntoIterator<Item = Dot>) -> Self {
+    let dots: Vec<Dot> = dots.into_iter().collect();
+    let width = dots.iter().map(|dot| dot.x as usize + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|dot| dot.y as usize + 1).max().unwrap_or(0);
+    let max_bits = dots.len().max(1) * Self::DENSE_BITS_PER_DOT;
+
+    match width.checked_mul(height) {
+      Some(bits) if bits <= max_bits => {
+        let mut grid = BitGrid::new(width, height);
+        for dot in dots {
+          grid.set((dot.y as usize, dot.x as usize));
+        }
+        Sheet::Dense(grid)
+      }
+      _ => Sheet::Sparse(dots.into_iter().collect()),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    match self {
+      Sheet::Sparse(dots) => dots.len(),
+      Sheet::Dense(grid) => grid.count(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn contains(&self, dot: &Dot) -> bool {
+    match self {
+      Sheet::Sparse(dots) => dots.contains(dot),
+      Sheet::Dense(grid) => {
+        let (row, col) = (dot.y as usize, dot.x as usize);
+        grid.contains((row, col)) && grid.get((row, col))
+      }
+    }
+  }
+
+  /// The dots, row by row for a dense sheet and in no order otherwise.
+  pub fn iter(&self) -> Box<dyn Iterator<Item = Dot> + '_> {
+    match self {
+      Sheet::Sparse(dots) => Box::new(dots.iter().copied()),
+      Sheet::Dense(grid) => Box::new(grid.iter().map(|(row, col)| Dot {
+        x: col as u32,
+        y: row as u32,
+      })),
+    }
+  }
+
+  /// None for a sheet without dots.
+  pub fn bounds(&self) -> Option<Bounds> {
+    let first = self.iter().next()?;
+    let mut bounds = Bounds {
+      top_left: first,
+      bottom_right: first,
+    };
+    for dot in self.iter() {
+      bounds.top_left.x = bounds.top_left.x.min(dot.x);
+      bounds.top_left.y = bounds.top_left.y.min(dot.y);
+      bounds.bottom_right.x = bounds.bottom_right.x.max(dot.x);
+      bounds.bottom_right.y = bounds.bottom_right.y.max(dot.y);
+    }
+    Some(bounds)
+  }
+
+  /// The sheet after `fold`, see `apply_fold_with`.
+  pub fn fold(&self, fold: Fold, on_fold_line: OnFoldLine) -> Result<Sheet> {
+    let line = fold.line();
+    let on_line = |dot: &Dot| fold.position(dot) == line;
+    if on_fold_line == OnFoldLine::Reject {
+      if let Some(dot) = self.iter().find(on_line) {
+        return Err(on_fold_line_error(dot, fold));
+      }
+    }
+
+    let shift = fold.shift(self.iter().filter(|dot| !on_line(dot)));
+    let dots = self.iter().filter(|dot| !on_line(dot));
+    Ok(Sheet::new(dots.map(|dot| fold.reflect(dot, shift))))
+  }
+
+  /// The sheet from the top left corner to the last dot, true for dots.
+  /// Fails for sheets spanning more than `MAX_GRID_CELLS`.
+  pub fn to_grid(&self) -> Result<Grid<bool>> {
+    let Bounds { bottom_right, .. } = self.bounds().unwrap_or(Bounds {
+      top_left: Dot { x: 0, y: 0 },
+      bottom_right: Dot { x: 0, y: 0 },
+    });
+    let (width, height) =
+      (bottom_right.x as u64 + 1, bottom_right.y as u64 + 1);
+    if width * height > Self::MAX_GRID_CELLS as u64 {
+      return Err(AocError::Unsupported(format!(
+        "A {}x{} sheet is too large to draw",
+        width, height
+      )));
+    }
+    let dots: Vec<Dot> = self.iter().collect();
+    Ok(dot_grid(dots.iter()))
+  }
+}
+
+/// The sheet before folding or after a fold.
+#[derive(Clone, Debug)]
+pub struct Stage {
+  /// None before the first fold.
+  pub fold: Option<Fold>,
+  pub dots: Sheet,
+}
+
+/// The smallest rectangle holding every dot, corners included.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bounds {
+  pub top_left: Dot,
+  pub bottom_right: Dot,
+}
+
+impl Stage {
+  pub fn visible(&self) -> usize {
+    self.dots.len()
+  }
+
+  pub fn bounds(&self) -> Option<Bounds> {
+    self.dots.bounds()
+  }
+}
+
+/// The fold and what the sheet looks like after it.
+impl fmt::Display for Stage {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    match self.fold {
+      Some(fold) => write!(formatter, "{}: ", fold)?,
+      None => write!(formatter, "before folding: ")?,
+    }
+    write!(formatter, "{} dots", self.visible())?;
+    if let Some(Bounds {
+      top_left,
+      bottom_right,
+    }) = self.bounds()
+    {
+      write!(formatter, " from {} to {}", top_left, bottom_right)?;
+    }
+    Ok(())
+  }
+}
+
+/// The sheet before folding, then after every fold.
+pub fn replay(
+  input: &DotsAndFolds,
+) -> impl Iterator<Item = Result<Stage>> + '_ {
+  replay_with(input, OnFoldLine::Drop)
+}
+
+/// Like `replay`, stopping at the first fold that fails.
+pub fn replay_with(
+  DotsAndFolds { dots, folds }: &DotsAndFolds,
+  on_fold_line: OnFoldLine,
+) -> impl Iterator<Item = Result<Stage>> + '_ {
+  let mut sheet = Sheet::new(dots.iter().copied());
+  let first = Stage {
+    fold: None,
+    dots: sheet.clone(),
+  };
+  let mut failed = false;
+
+  iter::once(Ok(first)).chain(folds.iter().map_while(move |&fold| {
+    if failed {
+      return None;
+    }
+    let stage = sheet.fold(fold, on_fold_line).map(|folded| {
+      sheet = folded;
+      Stage {
+        fold: Some(fold),
+        dots: sheet.clone(),
+      }
+    });
+    failed = stage.is_err();
+    Some(stage)
+  }))
+}
+
+/// The sheet from the top left corner to the last dot, true for dots.
+pub fn dot_grid<'a>(dots: impl Iterator<Item = &'a Dot> + Clone) -> Grid<bool> {
+  let width = dots
+    .clone()
+    .map(|dot| dot.x as usize + 1)
+    .max()
+    .unwrap_or(1);
+  let height = dots
+    .clone()
+    .map(|dot| dot.y as usize + 1)
+    .max()
+    .unwrap_or(1);
+  let mut grid = Grid::from_fn(width, height, |_| false);
+  for dot in dots {
+    grid[(dot.y as usize, dot.x as usize)] = true;
+  }
+  grid
+}
+
+/// Rows of `#` for dots and `.` for paper.
+pub fn sheet_text(grid: &Grid<bool>) -> String {
+  let mut text = String::new();
+  for row in grid.rows() {
+    text.extend(row.iter().map(|&dot| if dot { '#' } else { '.' }));
+    text.push('\n');
+  }
+  text
+}
+
+/// A plain PBM image, black for dots.
+pub fn render_pbm(grid: &Grid<bool>) -> String {
+  let mut image = format!("P1\n{} {}\n", grid.width(), grid.height());
+  for row in grid.rows() {
+    for (i, &dot) in row.iter().enumerate() {
+      let separator = if i == 0 { "" } else { " " };
+      write!(image, "{}{}", separator, u8::from(dot)).unwrap();
+    }
+    image.push('\n');
+  }
+  image
+}
+
+pub fn render_dots(mut dots: Vec<Dot>) -> String {
+  let mut rendered = String::new();
+  dots.sort();
+  dots.dedup();
+
+  let mut current = Dot { x: 0, y: 0 };
+
+  for dot in dots {
+    if current.y < dot.y {
+      for _ in current.y..dot.y {
+        rendered.push('\n');
+      }
+      current = Dot { x: 0, y: dot.y };
+    }
+    if current.x < dot.x {
+      for _ in current.x..dot.x {
+        rendered.push(' ');
+      }
+    }
+    rendered.push('#');
+    current.x = dot.x + 1;
+  }
+  rendered
+}
+
+fn on_fold_line_error(dot: Dot, fold: Fold) -> AocError {
+  AocError::NoAnswer(format!("dot {} lies on the line of {}", dot, fold))
+}
@@ -0,0 +1,367 @@
+use crate::grid::Grid;
+use crate::parse::Parser;
+use crate::solution::{Flag, Flags};
+use crate::{AocError, ParseResult, Result, Solution};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::mem;
+
+pub type ElementCount = HashMap<u8, usize>;
+pub type Pair = (u8, u8);
+pub type PairCounts = HashMap<Pair, usize>;
+pub type InsertionRules = HashMap<Pair, [Pair; 2]>;
+
+pub struct Day14;
+
+#[derive(Clone, Debug)]
+pub struct Polymer {
+  pub pair_counts: PairCounts,
+  pub rules: InsertionRules,
+  pub element_counts: ElementCount,
+  /// The last element of the template, which insertions never move.
+  pub last: u8,
+}
+
+impl Solution for Day14 {
+  type Input = Polymer;
+  type Answer1 = usize;
+  type Answer2 = usize;
+
+  const FLAGS: &'static [Flag] = &[
+    Flag {
+      name: "--steps",
+      value: Some("<n>"),
+      help: "Print the difference after <n> steps, exact up to ~120 steps",
+    },
+    Flag {
+      name: "--modulus",
+      value: Some("<m>"),
+      help: "Print the element counts modulo <m>, for --steps beyond ~120",
+    },
+  ];
+
+  fn parse(input: &str) -> Result<Polymer> {
+    Ok(parse_input(input)?)
+  }
+
+  fn part1(polymer: &Polymer) -> Result<usize> {
+    Ok(polymer_difference(polymer, 10))
+  }
+
+  fn part2(polymer: &Polymer) -> Result<usize> {
+    Ok(polymer_difference(polymer, 40))
+  }
+
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    let steps = flags.parse("--steps")?.ok_or_else(|| {
+      AocError::Unsupported("--modulus needs --steps".to_string())
+    })?;
+    let polymer = Self::parse(input)?;
+    match flags.parse("--modulus")? {
+      None => {
+        let difference =
+          matrix_difference(&polymer, steps).map_err(|err| match err {
+            AocError::NoAnswer(message) => {
+              AocError::NoAnswer(format!("{}, try --modulus", message))
+            }
+            err => err,
+          })?;
+        writeln!(out, "{}", difference)?;
+      }
+      Some(modulus) => {
+        let arithmetic = Arithmetic::Modulo(modulus);
+        for (element, count) in
+          element_counts_after(&polymer, steps, arithmetic)?
+        {
+          writeln!(out, "{} {}", element as char, count)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+pub fn polymer_difference(polymer: &Polymer, n: usize) -> usize {
+  let mut pair_counts = (polymer.pair_counts.clone(), PairCounts::new());
+
+  element_difference(
+    steps(n, &mut pair_counts, &polymer.rules),
+    polymer.element_counts.clone(),
+  )
+}
+
+pub fn steps<'a>(
+  n: usize,
+  pair_counts: &'a mut (PairCounts, PairCounts),
+  rules: &InsertionRules,
+) -> &'a PairCounts {
+  let first = &mut pair_counts.0;
+  let second = &mut pair_counts.1;
+
+  for _ in 0..n {
+    for (pair, count) in transform(rules, first) {
+      *second.entry(pair).or_insert(0) += count;
+    }
+    first.clear();
+    mem::swap(first, second);
+  }
+  first
+}
+
+pub fn transform<'a>(
+  rules: &'a InsertionRules,
+  pair_counts: &'a PairCounts,
+) -> impl Iterator<Item = (Pair, usize)> + 'a {
+  pair_counts.iter().flat_map(|(pair, count)| {
+    let pairs: [Pair; 2] = rules[pair];
+    [(pairs[0], *count), (pairs[1], *count)]
+  })
+}
+
+pub fn element_difference(
+  pair_counts: &PairCounts,
+  mut element_counts: ElementCount,
+) -> usize {
+  for (pair, count) in pair_counts {
+    *element_counts.entry(pair.0).or_insert(0) += count;
+    *element_counts.entry(pair.1).or_insert(0) += count;
+  }
+
+  let [min, max] = element_counts.iter().fold(
+    [('?', usize::MAX), ('?', 0)],
+    |[min, max], (element, count)| {
+      let count = count / 2;
+      [
+        order_counts(min, (*element as char, count), Ordering::Less),
+        order_counts(max, (*element as char, count), Ordering::Greater),
+      ]
+    },
+  );
+
+  max.1 - min.1
+}
+
+fn order_counts(
+  a: (char, usize),
+  b: (char, usize),
+  ordering: Ordering,
+) -> (char, usize) {
+  if a.1.cmp(&b.1) == ordering {
+    a
+  } else {
+    b
+  }
+}
+
+/// How `element_counts_after` adds and multiplies counts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arithmetic {
+  /// Exact counts, failing once they overflow a `u128`.
+  Exact,
+  /// Counts modulo the given modulus.
+  Modulo(u64),
+}
+
+impl Arithmetic {
+  fn add(self, a: u128, b: u128) -> Option<u128> {
+    match self {
+      Arithmetic::Exact => a.checked_add(b),
+      Arithmetic::Modulo(m) => Some((a + b) % u128::from(m)),
+    }
+  }
+
+  fn mul(self, a: u128, b: u128) -> Option<u128> {
+    match self {
+      Arithmetic::Exact => a.checked_mul(b),
+      // Both are below the modulus, so the product fits.
+      Arithmetic::Modulo(m) => Some(a * b % u128::from(m)),
+    }
+  }
+
+  fn reduce(self, a: u128) -> u128 {
+    match self {
+      Arithmetic::Exact => a,
+      Arithmetic::Modulo(m) => a % u128::from(m),
+    }
+  }
+}
+
+/// The pairs of `rules` in order, and the matrix taking counts of them one
+/// step further: each pair `from` yields `matrix[(to, from)]` pairs `to`.
+/// Fails without rules, or if a rule yields a pair without a rule of its own.
+pub fn transition_matrix(
+  rules: &InsertionRules,
+) -> Result<(Vec<Pair>, Grid<u128>)> {
+  if rules.is_empty() {
+    return Err(AocError::NoAnswer("no insertion rules".to_string()));
+  }
+  let mut pairs: Vec<Pair> = rules.keys().copied().collect();
+  pairs.sort_unstable();
+  let mut matrix = Grid::from_fn(pairs.len(), pairs.len(), |_| 0);
+  for (from, pair) in pairs.iter().enumerate() {
+    for to in &rules[pair] {
+      let to = pairs.binary_search(to).map_err(|_| no_rule(*to))?;
+      matrix[(to, from)] += 1;
+    }
+  }
+  Ok((pairs, matrix))
+}
+
+fn no_rule((a, b): Pair) -> AocError {
+  AocError::NoAnswer(format!("no rule for {}{}", a as char, b as char))
+}
+
+/// How often each element occurs after `steps` insertion steps, found by
+/// raising the transition matrix to the power of `steps`.
+pub fn element_counts_after(
+  polymer: &Polymer,
+  steps: u64,
+  arithmetic: Arithmetic,
+) -> Result<BTreeMap<u8, u128>> {
+  if arithmetic == Arithmetic::Modulo(0) {
+    return Err(AocError::Unsupported("Invalid modulus: 0".to_string()));
+  }
+  let overflow = || {
+    AocError::NoAnswer(format!("counts overflow u128 within {} steps", steps))
+  };
+
+  let (pairs, mut power) = transition_matrix(&polymer.rules)?;
+  if let Some(&pair) = polymer
+    .pair_counts
+    .keys()
+    .find(|pair| !polymer.rules.contains_key(pair))
+  {
+    return Err(no_rule(pair));
+  }
+  let mut counts: Vec<u128> = pairs
+    .iter()
+    .map(|pair| {
+      let count = polymer.pair_counts.get(pair).copied().unwrap_or(0);
+      arithmetic.reduce(count as u128)
+    })
+    .collect();
+
+  // Squaring only as far as the highest bit of `steps` keeps exact powers
+  // from overflowing needlessly.
+  let mut n = steps;
+  while n > 0 {
+    if n & 1 == 1 {
+      counts = multiply(&power, &counts, arithmetic).ok_or_else(overflow)?;
+    }
+    n >>= 1;
+    if n > 0 {
+      power = square(&power, arithmetic).ok_or_else(overflow)?;
+    }
+  }
+
+  // Each element but the last starts exactly one pair.
+  let mut element_counts = BTreeMap::from([(polymer.last, 1)]);
+  for (pair, count) in pairs.iter().zip(counts) {
+    let total = element_counts.entry(pair.0).or_insert(0);
+    *total = arithmetic.add(*total, count).ok_or_else(overflow)?;
+  }
+  Ok(element_counts)
+}
+
+/// The difference between the most and least common element after `steps`
+/// steps, like `polymer_difference` but for any number of steps that keeps
+/// the counts within a `u128`.
+pub fn matrix_difference(polymer: &Polymer, steps: u64) -> Result<u128> {
+  let counts = element_counts_after(polymer, steps, Arithmetic::Exact)?;
+  let present = counts.values().filter(|&&count| count > 0);
+  let max = present.clone().max().copied().unwrap_or(0);
+  let min = present.min().copied().unwrap_or(0);
+  Ok(max - min)
+}
+
+fn multiply(
+  matrix: &Grid<u128>,
+  counts: &[u128],
+  arithmetic: Arithmetic,
+) -> Option<Vec<u128>> {
+  matrix
+    .rows()
+    .map(|row| dot_product(row, counts, arithmetic))
+    .collect()
+}
+
+fn square(matrix: &Grid<u128>, arithmetic: Arithmetic) -> Option<Grid<u128>> {
+  let size = matrix.width();
+  let mut cells = Vec::with_capacity(size * size);
+  for row in matrix.rows() {
+    for col in 0..size {
+      cells.push(dot_product(row, matrix.column(col), arithmetic)?);
+    }
+  }
+  Some(Grid::new(size, cells))
+}
+
+fn dot_product<'a>(
+  a: &[u128],
+  b: impl IntoIterator<Item = &'a u128>,
+  arithmetic: Arithmetic,
+) -> Option<u128> {
+  a.iter().zip(b).try_fold(0, |sum, (&a, &b)| {
+    arithmetic.add(sum, arithmetic.mul(a, b)?)
+  })
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Polymer> {
+  let parser = Parser::new(input);
+  let [template, rule_lines] =
+    parser.split_sections(["a polymer template", "insertion rules"])?;
+  let polymer = elements(&parser, template.trim_end(), None)?;
+
+  let mut seen = HashSet::new();
+  let mut rules = InsertionRules::new();
+  for line in rule_lines.lines() {
+    let (from, to) = parser.rule(line)?;
+    let from = elements(&parser, from, Some(2))?;
+    let to = elements(&parser, to, Some(1))?[0];
+    let from = (from[0], from[1]);
+    seen.extend([from.0, from.1, to]);
+    rules.insert(from, [(from.0, to), (to, from.1)]);
+  }
+
+  seen.extend(polymer);
+
+  if rules.len() == seen.len().pow(2) {
+    let mut counts = PairCounts::new();
+    for pair in polymer.iter().copied().zip(polymer[1..].iter().copied()) {
+      *counts.entry(pair).or_insert(0) += 1;
+    }
+    Ok(Polymer {
+      pair_counts: counts,
+      rules,
+      element_counts: [(polymer[0], 1), (polymer[polymer.len() - 1], 1)].into(),
+      last: polymer[polymer.len() - 1],
+    })
+  } else {
+    Err(parser.error_at_end(format!(
+      "a rule for each of the {} element pairs",
+      seen.len().pow(2)
+    )))
+  }
+}
+
+/// The elements in `text`, which has to have `len` of them if given.
+fn elements<'a>(
+  parser: &Parser,
+  text: &'a str,
+  len: Option<usize>,
+) -> ParseResult<&'a [u8]> {
+  if let Some(bad) = text
+    .split(|c: char| c.is_ascii_uppercase())
+    .find(|s| !s.is_empty())
+  {
+    return Err(parser.error(bad, "an element from A to Z"));
+  }
+  match len {
+    Some(len) if text.len() != len => {
+      let plural = if len == 1 { "" } else { "s" };
+      Err(parser.error(text, format!("{} element{}", len, plural)))
+    }
+    _ => Ok(text.as_bytes()),
+  }
+}
@@ -0,0 +1,541 @@
+use crate::bitset::BitGrid;
+use crate::grid::{
+  self, Coord, Grid, EIGHT_NEIGHBOURS, FOUR_NEIGHBOURS, KNIGHT_MOVES,
+};
+use crate::parse::Parser;
+use crate::solution::{Flag, Flags};
+use crate::{AocError, Result, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Size {
+  pub width: usize,
+  pub height: usize,
+}
+
+pub type RiskMap = Grid<u8>;
+
+/// The risk of a field in a tiled map, from the base map, the tile's
+/// `(row, col)` among the tiles and the field's position within the tile.
+pub type TileTransform = fn(&RiskMap, Coord, Coord) -> u32;
+
+/// A map made of copies of a base map, computing each field's risk when
+/// asked for it.
+pub struct TiledMap<'a, F = TileTransform> {
+  map: &'a RiskMap,
+  /// The number of tiles across and down.
+  tiles: Size,
+  transform: F,
+}
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct FieldValue<U: Ord>(pub U, pub Coord);
+
+/// A lowest risk path, from the start to the end field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Route {
+  pub risk: u32,
+  pub path: Vec<Coord>,
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+  type Input = RiskMap;
+  type Answer1 = u32;
+  type Answer2 = u32;
+
+  const FLAGS: &'static [Flag] = &[
+    Flag {
+      name: "--render",
+      value: Some("<ansi|ppm>"),
+      help: "Draw the map with the lowest risk route highlighted",
+    },
+    Flag {
+      name: "--heuristic",
+      value: Some("<zero|manhattan>"),
+      help: "Heuristic for the A* search, zero by default",
+    },
+    Flag {
+      name: "--start",
+      value: Some("<row,col>"),
+      help: "Start field, the top left one by default",
+    },
+    Flag {
+      name: "--end",
+      value: Some("<row,col>"),
+      help: "End field, the bottom right one by default",
+    },
+    Flag {
+      name: "--moves",
+      value: Some("<4|8|knight>"),
+      help: "Moves between fields, 4 orthogonal ones by default",
+    },
+    Flag {
+      name: "--blocked",
+      value: Some("<row,col;...>"),
+      help: "Fields the route may not enter",
+    },
+    Flag {
+      name: "--tiled",
+      value: None,
+      help: "Use the 5x5 tiled map of part 2",
+    },
+    Flag {
+      name: "--output",
+      value: Some("<path>"),
+      help: "Write the drawing to <path> instead of stdout",
+    },
+  ];
+
+  fn parse(input: &str) -> Result<RiskMap> {
+    Ok(Parser::new(input).digit_grid(input, 1..=9, "a risk level")?)
+  }
+
+  fn part1(risk_map: &RiskMap) -> Result<u32> {
+    traverse(risk_map).ok_or_else(no_route)
+  }
+
+  fn part2(risk_map: &RiskMap) -> Result<u32> {
+    traverse(&TiledMap::new(5, risk_map)).ok_or_else(no_route)
+  }
+
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    let risk_map = Self::parse(input)?;
+    if flags.has("--tiled") {
+      run_on(&TiledMap::new(5, &risk_map), flags, out)
+    } else {
+      run_on(&risk_map, flags, out)
+    }
+  }
+}
+
+fn run_on<M: Map<u32>>(
+  risk_map: &M,
+  flags: &Flags,
+  out: &mut dyn io::Write,
+) -> Result<()> {
+  let heuristic = match flags.value("--heuristic") {
+    None | Some("zero") => Heuristic::Zero,
+    Some("manhattan") => Heuristic::Manhattan,
+    Some(other) => {
+      return Err(AocError::Unsupported(format!(
+        "Unknown heuristic {}, expected zero or manhattan",
+        other
+      )))
+    }
+  };
+
+  let mut rules = Rules::for_map(risk_map);
+  if let Some(start) = flags.value("--start") {
+    rules.start = parse_field("--start", start)?;
+  }
+  if let Some(end) = flags.value("--end") {
+    rules.end = parse_field("--end", end)?;
+  }
+  rules.moves = match flags.value("--moves") {
+    None | Some("4") => Moves::Four,
+    Some("8") => Moves::Eight,
+    Some("knight") => Moves::Knight,
+    Some(other) => {
+      return Err(AocError::Unsupported(format!(
+        "Unknown moves {}, expected 4, 8 or knight",
+        other
+      )))
+    }
+  };
+  if let Some(blocked) = flags.value("--blocked") {
+    for field in blocked.split(';').filter(|field| !field.is_empty()) {
+      rules.impassable.insert(parse_field("--blocked", field)?);
+    }
+  }
+
+  let started = Instant::now();
+  let search =
+    search_with(risk_map, &rules, &heuristic).ok_or_else(no_route)?;
+  let elapsed = started.elapsed();
+
+  let drawing = match flags.value("--render") {
+    Some("ansi") => render_ansi(risk_map, &search.route).into_bytes(),
+    Some("ppm") => render_ppm(risk_map, &search.route.path),
+    Some(other) => {
+      return Err(AocError::Unsupported(format!(
+        "Unknown format {}, expected ansi or ppm",
+        other
+      )))
+    }
+    None => {
+      writeln!(
+        out,
+        "risk {}, {} fields expanded in {:.1?}",
+        search.route.risk, search.expanded, elapsed
+      )?;
+      return Ok(());
+    }
+  };
+
+  match flags.value("--output") {
+    Some(path) => fs::write(path, drawing)?,
+    None => out.write_all(&drawing)?,
+  }
+  Ok(())
+}
+
+fn parse_field(flag: &str, value: &str) -> Result<Coord> {
+  value
+    .split_once(',')
+    .and_then(|(row, col)| Some((row.parse().ok()?, col.parse().ok()?)))
+    .ok_or_else(|| {
+      AocError::Unsupported(format!("Invalid field for {}: {}", flag, value))
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Moves {
+  /// To the fields above, below, left and right.
+  Four,
+  /// Diagonally as well.
+  Eight,
+  /// Like a chess knight.
+  Knight,
+}
+
+impl Moves {
+  pub fn offsets(self) -> &'static [(isize, isize)] {
+    match self {
+      Moves::Four => &FOUR_NEIGHBOURS,
+      Moves::Eight => &EIGHT_NEIGHBOURS,
+      Moves::Knight => &KNIGHT_MOVES,
+    }
+  }
+
+  /// The least number of moves between two fields on an open map.
+  pub fn distance(self, (r1, c1): Coord, (r2, c2): Coord) -> usize {
+    let (rows, cols) = (r1.abs_diff(r2), c1.abs_diff(c2));
+    match self {
+      Moves::Four => rows + cols,
+      Moves::Eight => rows.max(cols),
+      // A lower bound: a knight covers at most 2 rows or columns and 3 in
+      // total per move.
+      Moves::Knight => rows
+        .div_ceil(2)
+        .max(cols.div_ceil(2))
+        .max((rows + cols).div_ceil(3)),
+    }
+  }
+}
+
+/// Where a route starts and ends and how it may move.
+#[derive(Clone, Debug)]
+pub struct Rules {
+  pub start: Coord,
+  pub end: Coord,
+  pub moves: Moves,
+  /// Fields the route may not enter.
+  pub impassable: HashSet<Coord>,
+}
+
+impl Rules {
+  /// From the map's start to its end, moving orthogonally.
+  pub fn for_map<T: Ord, M: Map<T>>(map: &M) -> Self {
+    Self {
+      start: map.start(),
+      end: map.end(),
+      moves: Moves::Four,
+      impassable: HashSet::new(),
+    }
+  }
+}
+
+/// An estimate of the risk left from a field to the end. The search finds
+/// the lowest risk route as long as the estimate for a field never exceeds
+/// the risk of a neighbour plus the estimate for that neighbour.
+pub enum Heuristic<'a> {
+  /// No estimate, which makes the search Dijkstra's algorithm.
+  Zero,
+  /// The least number of moves to the end times the lowest risk on the map,
+  /// the Manhattan distance for orthogonal moves.
+  Manhattan,
+  Custom(&'a dyn Fn(Coord) -> u32),
+}
+
+impl Heuristic<'_> {
+  fn estimator<'a, M: Map<u32>>(
+    &'a self,
+    risk_map: &M,
+    rules: &Rules,
+  ) -> Box<dyn Fn(Coord) -> u32 + 'a> {
+    match self {
+      Heuristic::Zero => Box::new(|_| 0),
+      Heuristic::Manhattan => {
+        let Size { width, height } = risk_map.size();
+        let min_risk = (0..height)
+          .flat_map(|row| (0..width).map(move |col| (row, col)))
+          .map(|field| risk_map.get(field))
+          .min()
+          .unwrap_or(0);
+        let (end, moves) = (rules.end, rules.moves);
+        Box::new(move |field| moves.distance(field, end) as u32 * min_risk)
+      }
+      Heuristic::Custom(estimate) => Box::new(estimate),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Search {
+  pub route: Route,
+  /// The number of fields whose neighbours were looked at.
+  pub expanded: usize,
+}
+
+pub fn traverse<M: Map<u32>>(risk_map: &M) -> Option<u32> {
+  lowest_risk_route(risk_map).map(|route| route.risk)
+}
+
+pub fn lowest_risk_route<M: Map<u32>>(risk_map: &M) -> Option<Route> {
+  search(risk_map, &Heuristic::Zero).map(|search| search.route)
+}
+
+/// A* search from the start to the end of `risk_map`, None if the end
+/// cannot be reached.
+pub fn search<M: Map<u32>>(
+  risk_map: &M,
+  heuristic: &Heuristic,
+) -> Option<Search> {
+  search_with(risk_map, &Rules::for_map(risk_map), heuristic)
+}
+
+/// A* search following `rules`, None if the end cannot be reached.
+pub fn search_with<M: Map<u32>>(
+  risk_map: &M,
+  rules: &Rules,
+  heuristic: &Heuristic,
+) -> Option<Search> {
+  let Size { width, height } = risk_map.size();
+  let Rules { start, end, .. } = *rules;
+  let passable = |(row, col): Coord| {
+    row < height && col < width && !rules.impassable.contains(&(row, col))
+  };
+  if !passable(start) || !passable(end) {
+    return None;
+  }
+
+  let estimate = heuristic.estimator(risk_map, rules);
+  let mut expanded = BitGrid::new(width, height);
+  let mut risks = Grid::from_fn(width, height, |_| u32::MAX);
+  let mut previous = Grid::from_fn(width, height, |_| None);
+  // Queued by estimated total risk, ties going to the field estimated to
+  // be closest to the end.
+  let mut open_paths = BinaryHeap::from([Reverse(FieldValue(
+    (estimate(start), estimate(start)),
+    start,
+  ))]);
+  risks[start] = 0;
+  let mut num_expanded = 0;
+
+  while let Some(Reverse(FieldValue(_, field))) = open_paths.pop() {
+    // Fields are queued again when a cheaper path to them shows up, the
+    // older entries are left behind.
+    if expanded.get(field) {
+      continue;
+    }
+    if field == end {
+      break;
+    }
+    expanded.set(field);
+    num_expanded += 1;
+
+    let offsets = rules.moves.offsets();
+    for neighbour_field in grid::neighbours(width, height, field, offsets)
+      .filter(|&field| passable(field))
+    {
+      let risk = risks[field] + risk_map.get(neighbour_field);
+      if risk < risks[neighbour_field] {
+        risks[neighbour_field] = risk;
+        previous[neighbour_field] = Some(field);
+        let left = estimate(neighbour_field);
+        let value = (risk + left, left);
+        open_paths.push(Reverse(FieldValue(value, neighbour_field)));
+      }
+    }
+  }
+
+  if risks[end] == u32::MAX {
+    return None;
+  }
+
+  let mut path = vec![end];
+  while let Some(field) = previous[*path.last().unwrap()] {
+    path.push(field);
+  }
+  path.reverse();
+
+  Some(Search {
+    route: Route {
+      risk: risks[end],
+      path,
+    },
+    expanded: num_expanded,
+  })
+}
+
+fn no_route() -> AocError {
+  AocError::NoAnswer("no route to the end".to_string())
+}
+
+/// The risk levels as digits with the route in bold on a red background,
+/// followed by a summary line.
+pub fn render_ansi<M: Map<u32>>(risk_map: &M, route: &Route) -> String {
+  let Size { width, height } = risk_map.size();
+  let on_path = route_grid(width, height, &route.path);
+
+  let mut rendered = String::new();
+  for row in 0..height {
+    for col in 0..width {
+      let risk = risk_map.get((row, col));
+      if on_path[(row, col)] {
+        write!(rendered, "\x1b[1;97;41m{}\x1b[0m", risk).unwrap();
+      } else {
+        write!(rendered, "\x1b[2m{}\x1b[0m", risk).unwrap();
+      }
+    }
+    rendered.push('\n');
+  }
+  writeln!(
+    rendered,
+    "risk {} over {} fields",
+    route.risk,
+    route.path.len()
+  )
+  .unwrap();
+  rendered
+}
+
+/// A binary PPM image with a pixel per field, darker for higher risk and
+/// red along `path`.
+pub fn render_ppm<M: Map<u32>>(risk_map: &M, path: &[Coord]) -> Vec<u8> {
+  let Size { width, height } = risk_map.size();
+  let on_path = route_grid(width, height, path);
+
+  let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+  for row in 0..height {
+    for col in 0..width {
+      if on_path[(row, col)] {
+        image.extend([255, 0, 0]);
+      } else {
+        let shade = 255 - 25 * risk_map.get((row, col)).min(9) as u8;
+        image.extend([shade; 3]);
+      }
+    }
+  }
+  image
+}
+
+fn route_grid(width: usize, height: usize, path: &[Coord]) -> Grid<bool> {
+  let mut on_path = Grid::from_fn(width, height, |_| false);
+  for &field in path {
+    on_path[field] = true;
+  }
+  on_path
+}
+
+pub trait Map<T: Ord> {
+  fn get(&self, field: Coord) -> T;
+  fn size(&self) -> Size;
+
+  fn start(&self) -> Coord {
+    (0, 0)
+  }
+  fn end(&self) -> Coord {
+    let size = self.size();
+    (size.height - 1, size.width - 1)
+  }
+
+  fn neighbours(&self, field: Coord) -> impl Iterator<Item = Coord> {
+    let Size { width, height } = self.size();
+    grid::neighbours(width, height, field, &FOUR_NEIGHBOURS)
+  }
+}
+
+impl Map<u32> for RiskMap {
+  fn size(&self) -> Size {
+    Size {
+      width: self.width(),
+      height: self.height(),
+    }
+  }
+
+  fn get(&self, field: Coord) -> u32 {
+    self[field].into()
+  }
+}
+
+impl Map<bool> for BitGrid {
+  fn get(&self, field: Coord) -> bool {
+    BitGrid::get(self, field)
+  }
+
+  fn size(&self) -> Size {
+    Size {
+      width: self.width(),
+      height: self.height(),
+    }
+  }
+}
+
+impl<'a> TiledMap<'a> {
+  /// `repetitions` tiles across and down, each one's risks one higher than
+  /// those of the tile left of or above it, wrapping from 9 back to 1.
+  pub fn new(repetitions: usize, map: &'a RiskMap) -> Self {
+    let tiles = Size {
+      width: repetitions,
+      height: repetitions,
+    };
+    Self::with_transform(map, tiles, increase_risk)
+  }
+}
+
+impl<'a, F: Fn(&RiskMap, Coord, Coord) -> u32> TiledMap<'a, F> {
+  pub fn with_transform(map: &'a RiskMap, tiles: Size, transform: F) -> Self {
+    Self {
+      map,
+      tiles,
+      transform,
+    }
+  }
+}
+
+/// The tiling rule of the puzzle.
+pub fn increase_risk(
+  map: &RiskMap,
+  (tile_row, tile_col): Coord,
+  field: Coord,
+) -> u32 {
+  let base = u32::from(map[field]);
+  (base + tile_row as u32 + tile_col as u32 - 1) % 9 + 1
+}
+
+impl<F: Fn(&RiskMap, Coord, Coord) -> u32> Map<u32> for TiledMap<'_, F> {
+  fn get(&self, field: Coord) -> u32 {
+    let Size { width, height } = self.map.size();
+    let tile = (field.0 / height, field.1 / width);
+    if tile.0 < self.tiles.height && tile.1 < self.tiles.width {
+      (self.transform)(self.map, tile, (field.0 % height, field.1 % width))
+    } else {
+      panic!("field {:?} out of bounds, size is {:?}", field, self.size());
+    }
+  }
+  fn size(&self) -> Size {
+    let mut size = self.map.size();
+    size.width *= self.tiles.width;
+    size.height *= self.tiles.height;
+    size
+  }
+}
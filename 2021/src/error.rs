@@ -0,0 +1,149 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Debug)]
+pub enum AocError {
+  Io(io::Error),
+  Parse(ParseError),
+  /// The input is well formed, but has no answer, e.g. no bingo board wins.
+  NoAnswer(String),
+  /// A request that cannot be served, e.g. a day without a solution or an
+  /// unknown command line argument.
+  Unsupported(String),
+}
+
+/// Input that does not match the puzzle format, with enough context to
+/// point at the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+  pub day: Option<u8>,
+  /// 1-based line number.
+  pub line: usize,
+  /// 1-based column, counted in characters.
+  pub column: usize,
+  /// The full line containing the error.
+  pub text: String,
+  /// The offending part of `text`.
+  pub token: String,
+  /// Description of what would have been valid here.
+  pub expected: String,
+}
+
+impl ParseError {
+  /// An error at `token`, which has to be a slice of `input`. Tokens from
+  /// elsewhere are reported at the end of `input`.
+  pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+    let start = input.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+      .checked_sub(start)
+      .filter(|offset| offset + token.len() <= input.len());
+
+    match offset {
+      Some(offset) => Self::at_offset(input, offset, token, expected),
+      None => Self::at_end(input, expected),
+    }
+  }
+
+  pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+    Self::at_offset(input, input.len(), "", expected)
+  }
+
+  fn at_offset(
+    input: &str,
+    offset: usize,
+    token: &str,
+    expected: impl Into<String>,
+  ) -> Self {
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[offset..]
+      .find('\n')
+      .map_or(input.len(), |i| offset + i);
+    let text = &input[line_start..line_end];
+
+    Self {
+      day: None,
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count() + 1,
+      text: text.trim_end_matches('\r').to_string(),
+      token: token.lines().next().unwrap_or("").to_string(),
+      expected: expected.into(),
+    }
+  }
+
+  pub fn with_day(self, day: u8) -> Self {
+    Self {
+      day: Some(day),
+      ..self
+    }
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    if let Some(day) = self.day {
+      write!(formatter, "day {:02}, ", day)?;
+    }
+    write!(
+      formatter,
+      "line {}, column {}: expected {}",
+      self.line, self.column, self.expected
+    )?;
+    if !self.token.is_empty() {
+      write!(formatter, ", found `{}`", self.token)?;
+    }
+
+    let gutter = " ".repeat(self.line.to_string().len());
+    write!(
+      formatter,
+      "\n{} |\n{} | {}\n{} | {}{}",
+      gutter,
+      self.line,
+      self.text,
+      gutter,
+      " ".repeat(self.column - 1),
+      "^".repeat(self.token.chars().count().max(1))
+    )
+  }
+}
+
+impl fmt::Display for AocError {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      AocError::Io(err) => err.fmt(formatter),
+      AocError::Parse(err) => err.fmt(formatter),
+      AocError::NoAnswer(message) => {
+        write!(formatter, "no answer, {}", message)
+      }
+      AocError::Unsupported(message) => formatter.write_str(message),
+    }
+  }
+}
+
+impl error::Error for ParseError {}
+
+impl error::Error for AocError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      AocError::Io(err) => Some(err),
+      AocError::Parse(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for AocError {
+  fn from(err: io::Error) -> Self {
+    AocError::Io(err)
+  }
+}
+
+impl From<ParseError> for AocError {
+  fn from(err: ParseError) -> Self {
+    AocError::Parse(err)
+  }
+}
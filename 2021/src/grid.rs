@@ -0,0 +1,218 @@
+use crate::parse::Parser;
+use crate::ParseResult;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+/// A `(row, col)` position in a grid.
+pub type Coord = (usize, usize);
+
+/// Row and column offsets of the neighbours sharing an edge with a cell,
+/// clockwise from the top.
+pub const FOUR_NEIGHBOURS: [(isize, isize); 4] =
+  [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Row and column offsets of all cells touching a cell, row by row.
+pub const EIGHT_NEIGHBOURS: [(isize, isize); 8] = [
+  (-1, -1),
+  (-1, 0),
+  (-1, 1),
+  (0, -1),
+  (0, 1),
+  (1, -1),
+  (1, 0),
+  (1, 1),
+];
+
+/// Row and column offsets of a chess knight's moves, clockwise from the top.
+pub const KNIGHT_MOVES: [(isize, isize); 8] = [
+  (-2, 1),
+  (-1, 2),
+  (1, 2),
+  (2, 1),
+  (2, -1),
+  (1, -2),
+  (-1, -2),
+  (-2, -1),
+];
+
+/// A rectangle of cells stored in row-major order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// A grid of `width` columns holding `cells` in row-major order.
+  ///
+  /// Panics if `cells` does not fill complete rows.
+  pub fn new(width: usize, cells: Vec<T>) -> Self {
+    assert!(
+      width > 0 && cells.len().is_multiple_of(width),
+      "{} cells do not fill rows of width {}",
+      cells.len(),
+      width
+    );
+    Self {
+      width,
+      height: cells.len() / width,
+      cells,
+    }
+  }
+
+  pub fn from_fn(
+    width: usize,
+    height: usize,
+    mut f: impl FnMut(Coord) -> T,
+  ) -> Self {
+    let cells = (0..height)
+      .flat_map(|row| (0..width).map(move |col| (row, col)))
+      .map(&mut f)
+      .collect();
+    Self::new(width, cells)
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, (row, col): Coord) -> bool {
+    row < self.height && col < self.width
+  }
+
+  pub fn get(&self, coord: Coord) -> Option<&T> {
+    if self.contains(coord) {
+      Some(&self[coord])
+    } else {
+      None
+    }
+  }
+
+  pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+    if self.contains(coord) {
+      Some(&mut self[coord])
+    } else {
+      None
+    }
+  }
+
+  /// All coordinates, row by row.
+  pub fn coords(&self) -> impl Iterator<Item = Coord> {
+    let width = self.width;
+    (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+  }
+
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.cells.iter()
+  }
+
+  pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+    self.cells.iter_mut()
+  }
+
+  pub fn enumerate(&self) -> impl Iterator<Item = (Coord, &T)> {
+    self.coords().zip(self.cells.iter())
+  }
+
+  pub fn enumerate_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+    self.coords().zip(self.cells.iter_mut())
+  }
+
+  pub fn row(&self, row: usize) -> &[T] {
+    &self.cells[row * self.width..(row + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> slice::Chunks<'_, T> {
+    self.cells.chunks(self.width)
+  }
+
+  pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+    assert!(col < self.width, "column {} out of bounds", col);
+    self.cells[col..].iter().step_by(self.width)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(move |col| self.column(col))
+  }
+
+  /// The coordinates at `offsets` from `coord` that lie inside the grid.
+  pub fn neighbours<'a>(
+    &self,
+    coord: Coord,
+    offsets: &'a [(isize, isize)],
+  ) -> impl Iterator<Item = Coord> + 'a {
+    neighbours(self.width, self.height, coord, offsets)
+  }
+
+  pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+    self.neighbours(coord, &FOUR_NEIGHBOURS)
+  }
+
+  pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+    self.neighbours(coord, &EIGHT_NEIGHBOURS)
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+}
+
+impl Grid<u8> {
+  /// Parses rows of digits from 0 to 9, see `Parser::digit_grid` for other
+  /// ranges.
+  pub fn parse_digits(input: &str) -> ParseResult<Self> {
+    Parser::new(input).digit_grid(input, 0..=9, "a digit")
+  }
+}
+
+/// The coordinates at `offsets` from `coord` inside a grid of the given
+/// size, for grids that are not stored as a `Grid`.
+pub fn neighbours(
+  width: usize,
+  height: usize,
+  (row, col): Coord,
+  offsets: &[(isize, isize)],
+) -> impl Iterator<Item = Coord> + '_ {
+  offsets.iter().filter_map(move |&(d_row, d_col)| {
+    let row = row.checked_add_signed(d_row).filter(|&r| r < height)?;
+    let col = col.checked_add_signed(d_col).filter(|&c| c < width)?;
+    Some((row, col))
+  })
+}
+
+impl<T> Index<Coord> for Grid<T> {
+  type Output = T;
+  fn index(&self, (row, col): Coord) -> &T {
+    assert!(col < self.width, "column {} out of bounds", col);
+    &self.cells[row * self.width + col]
+  }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+  fn index_mut(&mut self, (row, col): Coord) -> &mut T {
+    assert!(col < self.width, "column {} out of bounds", col);
+    &mut self.cells[row * self.width + col]
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        cell.fmt(formatter)?;
+      }
+      writeln!(formatter)?;
+    }
+    Ok(())
+  }
+}
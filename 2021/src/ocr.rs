@@ -0,0 +1,77 @@
+//! Reads the block capitals that some puzzles draw instead of printing an
+//! answer. Letters are 4 columns wide (Y takes 5) and 6 rows high, with
+//! one blank column after each.
+
+use crate::grid::Grid;
+use crate::{AocError, Result};
+use std::fmt::Write as _;
+
+pub const GLYPH_WIDTH: usize = 5;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Every letter seen in puzzle outputs so far, as rows of `#` and `.`
+/// padded to the glyph width.
+pub const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 18] = [
+  ('A', [".##..", "#..#.", "#..#.", "####.", "#..#.", "#..#."]),
+  ('B', ["###..", "#..#.", "###..", "#..#.", "#..#.", "###.."]),
+  ('C', [".##..", "#..#.", "#....", "#....", "#..#.", ".##.."]),
+  ('E', ["####.", "#....", "###..", "#....", "#....", "####."]),
+  ('F', ["####.", "#....", "###..", "#....", "#....", "#...."]),
+  ('G', [".##..", "#..#.", "#....", "#.##.", "#..#.", ".###."]),
+  ('H', ["#..#.", "#..#.", "####.", "#..#.", "#..#.", "#..#."]),
+  ('I', [".###.", "..#..", "..#..", "..#..", "..#..", ".###."]),
+  ('J', ["..##.", "...#.", "...#.", "...#.", "#..#.", ".##.."]),
+  ('K', ["#..#.", "#.#..", "##...", "#.#..", "#.#..", "#..#."]),
+  ('L', ["#....", "#....", "#....", "#....", "#....", "####."]),
+  ('O', [".##..", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+  ('P', ["###..", "#..#.", "#..#.", "###..", "#....", "#...."]),
+  ('R', ["###..", "#..#.", "#..#.", "###..", "#.#..", "#..#."]),
+  ('S', [".###.", "#....", "#....", ".##..", "...#.", "###.."]),
+  ('U', ["#..#.", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+  ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+  ('Z', ["####.", "...#.", "..#..", ".#...", "#....", "####."]),
+];
+
+/// The letters drawn by the set cells of `pixels`, from the top left
+/// corner. Fails listing every glyph that is not a known letter.
+pub fn read(pixels: &Grid<bool>) -> Result<String> {
+  if pixels.height() > GLYPH_HEIGHT {
+    return Err(AocError::NoAnswer(format!(
+      "{} rows of pixels, letters take {}",
+      pixels.height(),
+      GLYPH_HEIGHT
+    )));
+  }
+
+  let mut letters = String::new();
+  let mut unrecognised = String::new();
+  for i in 0..pixels.width().div_ceil(GLYPH_WIDTH) {
+    let glyph: Vec<String> = (0..GLYPH_HEIGHT)
+      .map(|row| {
+        (0..GLYPH_WIDTH)
+          .map(|col| (row, i * GLYPH_WIDTH + col))
+          .map(|pixel| match pixels.get(pixel) {
+            Some(true) => '#',
+            _ => '.',
+          })
+          .collect()
+      })
+      .collect();
+
+    match GLYPHS.iter().find(|(_, rows)| *rows == glyph[..]) {
+      Some(&(letter, _)) => letters.push(letter),
+      None => {
+        write!(unrecognised, "\nglyph {}:\n{}", i, glyph.join("\n")).unwrap()
+      }
+    }
+  }
+
+  if unrecognised.is_empty() {
+    Ok(letters)
+  } else {
+    Err(AocError::NoAnswer(format!(
+      "unrecognised letters{}",
+      unrecognised
+    )))
+  }
+}
@@ -0,0 +1,195 @@
+//! Parsers for the shapes that keep coming back in puzzle inputs. Every
+//! slice they return points into the original input, so a `ParseError`
+//! created from one knows its line and column.
+
+use crate::grid::Grid;
+use crate::{ParseError, ParseResult};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Parser<'a> {
+  input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+  pub fn new(input: &'a str) -> Self {
+    Self { input }
+  }
+
+  pub fn input(&self) -> &'a str {
+    self.input
+  }
+
+  /// An error at `token`, which has to be a slice of the input.
+  pub fn error(&self, token: &str, expected: impl Into<String>) -> ParseError {
+    ParseError::at(self.input, token, expected)
+  }
+
+  pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+    ParseError::at_end(self.input, expected)
+  }
+
+  pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+    self.input.lines()
+  }
+
+  /// The blocks of lines separated by blank lines.
+  pub fn sections(&self) -> Vec<&'a str> {
+    let mut sections = vec![];
+    let mut current: Option<(usize, usize)> = None;
+
+    for line in self.input.lines() {
+      let start = self.offset(line);
+      if line.trim().is_empty() {
+        if let Some((start, end)) = current.take() {
+          sections.push(&self.input[start..end]);
+        }
+      } else {
+        let (_, end) = current.get_or_insert((start, start));
+        *end = start + line.len();
+      }
+    }
+
+    if let Some((start, end)) = current {
+      sections.push(&self.input[start..end]);
+    }
+    sections
+  }
+
+  /// Exactly `N` blank line separated sections, `expected` describes each
+  /// of them.
+  pub fn split_sections<const N: usize>(
+    &self,
+    expected: [&str; N],
+  ) -> ParseResult<[&'a str; N]> {
+    let sections = self.sections();
+    if let Some(extra) = sections.get(N) {
+      return Err(self.error(extra, "end of input"));
+    }
+
+    let mut result = [""; N];
+    for (i, section) in result.iter_mut().enumerate() {
+      *section = sections
+        .get(i)
+        .ok_or_else(|| self.error_at_end(expected[i]))?;
+    }
+    Ok(result)
+  }
+
+  pub fn number<T: FromStr>(
+    &self,
+    token: &'a str,
+    expected: &str,
+  ) -> ParseResult<T> {
+    token.parse().map_err(|_| self.error(token, expected))
+  }
+
+  /// The items of `text` separated by `separator`, each parsed by `item`.
+  pub fn separated<T>(
+    &self,
+    text: &'a str,
+    separator: &str,
+    item: impl FnMut(&'a str) -> ParseResult<T>,
+  ) -> ParseResult<Vec<T>> {
+    text.split(separator).map(item).collect()
+  }
+
+  /// Comma separated integers, e.g. `3,4,3,1,2`.
+  pub fn integers<T: FromStr>(
+    &self,
+    text: &'a str,
+    expected: &str,
+  ) -> ParseResult<Vec<T>> {
+    self.separated(text.trim(), ",", |n| self.number(n, expected))
+  }
+
+  /// Exactly `N` whitespace separated words.
+  pub fn words<const N: usize>(
+    &self,
+    text: &'a str,
+    expected: [&str; N],
+  ) -> ParseResult<[&'a str; N]> {
+    let mut words = text.split_whitespace();
+    let mut result = [""; N];
+    for (i, word) in result.iter_mut().enumerate() {
+      *word = words
+        .next()
+        .ok_or_else(|| self.error(&text[text.len()..], expected[i]))?;
+    }
+
+    match words.next() {
+      Some(extra) => Err(self.error(extra, "end of line")),
+      None => Ok(result),
+    }
+  }
+
+  /// `text` split at the first `separator`.
+  pub fn split(
+    &self,
+    text: &'a str,
+    separator: &str,
+    expected: &str,
+  ) -> ParseResult<(&'a str, &'a str)> {
+    text
+      .split_once(separator)
+      .ok_or_else(|| self.error(text, expected))
+  }
+
+  /// An edge between two nodes, e.g. `start-A`.
+  pub fn edge(&self, line: &'a str) -> ParseResult<(&'a str, &'a str)> {
+    self.split(line.trim_end(), "-", "an edge `a-b`")
+  }
+
+  /// A rule mapping one side to the other, e.g. `CH -> B`.
+  pub fn rule(&self, line: &'a str) -> ParseResult<(&'a str, &'a str)> {
+    self.split(line.trim_end(), " -> ", "a rule `X -> Y`")
+  }
+
+  /// `text` without `prefix`.
+  pub fn prefixed(&self, text: &'a str, prefix: &str) -> ParseResult<&'a str> {
+    text
+      .strip_prefix(prefix)
+      .ok_or_else(|| self.error(text, format!("`{}`", prefix)))
+  }
+
+  /// A rectangle of single digits, one row per line. `cell` names a digit in
+  /// error messages.
+  pub fn digit_grid(
+    &self,
+    text: &'a str,
+    digits: RangeInclusive<u8>,
+    cell: &str,
+  ) -> ParseResult<Grid<u8>> {
+    let mut width = None;
+    let mut cells = vec![];
+
+    for line in text.lines() {
+      let row = line.trim_end();
+      for (i, c) in row.char_indices() {
+        match c.to_digit(10).map(|d| d as u8) {
+          Some(d) if digits.contains(&d) => cells.push(d),
+          _ => {
+            let expected =
+              format!("{} from {} to {}", cell, digits.start(), digits.end());
+            return Err(self.error(&row[i..i + c.len_utf8()], expected));
+          }
+        }
+      }
+
+      let width = *width.get_or_insert(row.len());
+      if row.len() != width {
+        return Err(self.error(row, format!("a row of {} digits", width)));
+      }
+    }
+
+    match width {
+      None | Some(0) => Err(self.error_at_end("a row of digits")),
+      Some(width) => Ok(Grid::new(width, cells)),
+    }
+  }
+
+  fn offset(&self, token: &str) -> usize {
+    token.as_ptr() as usize - self.input.as_ptr() as usize
+  }
+}
@@ -0,0 +1,164 @@
+//! The registry of all puzzle solutions. Binaries look days up here instead
+//! of calling into the day modules directly.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use crate::bench;
+use crate::solution::{DynSolution, Flag, Flags};
+use crate::{AocError, Result};
+
+use crate::{
+  day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11,
+  day12, day13, day14, day15,
+};
+
+pub struct Day {
+  pub number: u8,
+  pub solution: &'static dyn DynSolution,
+}
+
+macro_rules! day {
+  ($number:expr, $solution:expr) => {
+    Day {
+      number: $number,
+      solution: &$solution,
+    }
+  };
+}
+
+pub static DAYS: [Day; 15] = [
+  day!(1, day01::Day01),
+  day!(2, day02::Day02),
+  day!(3, day03::Day03),
+  day!(4, day04::Day04),
+  day!(5, day05::Day05),
+  day!(6, day06::Day06),
+  day!(7, day07::Day07),
+  day!(8, day08::Day08),
+  day!(9, day09::Day09),
+  day!(10, day10::Day10),
+  day!(11, day11::Day11),
+  day!(12, day12::Day12),
+  day!(13, day13::Day13),
+  day!(14, day14::Day14),
+  day!(15, day15::Day15),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+  DAYS.iter().find(|day| day.number == number)
+}
+
+impl Day {
+  /// The puzzle input checked in under `data/`.
+  pub fn default_input(&self) -> PathBuf {
+    [
+      env!("CARGO_MANIFEST_DIR"),
+      "data",
+      &format!("day-{:02}", self.number),
+    ]
+    .iter()
+    .collect()
+  }
+
+  pub fn solve(&self, part: u8, input: &str) -> Result<Answer> {
+    Ok(Answer {
+      day: self.number,
+      part,
+      value: self
+        .solution
+        .solve(part, input)
+        .map_err(|err| self.attach_day(err))?,
+    })
+  }
+
+  pub fn flags(&self) -> &'static [Flag] {
+    self.solution.flags()
+  }
+
+  pub fn run(
+    &self,
+    input: &str,
+    flags: &Flags,
+    out: &mut dyn io::Write,
+  ) -> Result<()> {
+    self
+      .solution
+      .run(input, flags, out)
+      .map_err(|err| self.attach_day(err))
+  }
+
+  fn attach_day(&self, err: AocError) -> AocError {
+    match err {
+      AocError::Parse(err) => AocError::Parse(err.with_day(self.number)),
+      err => err,
+    }
+  }
+
+  pub fn bench(
+    &self,
+    input: &str,
+    settings: bench::Settings,
+  ) -> Result<bench::Report> {
+    Ok(bench::Report {
+      day: self.number,
+      timings: self.solution.bench(input, settings)?,
+    })
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Answer {
+  pub day: u8,
+  pub part: u8,
+  pub value: String,
+}
+
+impl fmt::Display for Answer {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    let separator = if self.value.contains('\n') { '\n' } else { ' ' };
+    write!(
+      formatter,
+      "Day {:02}, part {}:{}{}",
+      self.day, self.part, separator, self.value
+    )
+  }
+}
+
+pub fn read_input(day: &Day, path: Option<PathBuf>) -> Result<String> {
+  let path = path.unwrap_or_else(|| day.default_input());
+  fs::read_to_string(&path).map_err(|err| {
+    io::Error::new(err.kind(), format!("{}: {}", path.display(), err)).into()
+  })
+}
+
+/// Entry point for the per-day binaries: reads the puzzle input from stdin
+/// and prints the answers to `parts`.
+pub fn run_stdin(number: u8, parts: &[u8]) -> ExitCode {
+  let run = || -> Result<()> {
+    let day = find(number).ok_or_else(|| bad_day(number))?;
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    for &part in parts {
+      println!("{}", day.solve(part, &input)?);
+    }
+    Ok(())
+  };
+
+  match run() {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(err) => {
+      eprintln!("Error: {}", err);
+      ExitCode::FAILURE
+    }
+  }
+}
+
+pub fn bad_day(number: u8) -> AocError {
+  AocError::Unsupported(format!("No solution for day {}", number))
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use crate::bench;
+use crate::{AocError, Result};
+
+/// A solution to one day's puzzle, split into parsing the puzzle input and
+/// solving each of the two parts with the parsed input.
+pub trait Solution {
+  type Input;
+  type Answer1: fmt::Display;
+  type Answer2: fmt::Display;
+
+  /// Flags `aoc run` accepts for this day on top of its own.
+  const FLAGS: &'static [Flag] = &[];
+
+  fn parse(input: &str) -> Result<Self::Input>;
+  fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+  fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+  /// Runs whatever `flags` ask for instead of the two parts, writing to
+  /// `out`. Only called with at least one of `FLAGS` given.
+  fn run(input: &str, flags: &Flags, out: &mut dyn io::Write) -> Result<()> {
+    let _ = (input, flags, out);
+    Err(AocError::Unsupported(
+      "No extra flags for this day".to_string(),
+    ))
+  }
+}
+
+/// A day specific command line flag, `value` names its argument if it
+/// takes one.
+#[derive(Clone, Copy, Debug)]
+pub struct Flag {
+  pub name: &'static str,
+  pub value: Option<&'static str>,
+  pub help: &'static str,
+}
+
+impl fmt::Display for Flag {
+  fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    let usage = match self.value {
+      Some(value) => format!("{} {}", self.name, value),
+      None => self.name.to_string(),
+    };
+    write!(formatter, "{:<20} {}", usage, self.help)
+  }
+}
+
+/// The day specific flags given on the command line, with their values.
+#[derive(Clone, Debug, Default)]
+pub struct Flags<'a> {
+  flags: Vec<(&'a str, Option<&'a str>)>,
+}
+
+impl<'a> Flags<'a> {
+  pub fn new(flags: Vec<(&'a str, Option<&'a str>)>) -> Self {
+    Self { flags }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.flags.is_empty()
+  }
+
+  pub fn has(&self, name: &str) -> bool {
+    self.flags.iter().any(|(flag, _)| *flag == name)
+  }
+
+  /// The value of the last `name` flag.
+  pub fn value(&self, name: &str) -> Option<&'a str> {
+    self
+      .flags
+      .iter()
+      .rev()
+      .find(|(flag, _)| *flag == name)
+      .and_then(|(_, value)| *value)
+  }
+
+  pub fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+    self
+      .value(name)
+      .map(|value| {
+        value.parse().map_err(|_| {
+          AocError::Unsupported(format!(
+            "Invalid value for {}: {}",
+            name, value
+          ))
+        })
+      })
+      .transpose()
+  }
+}
+
+/// Object safe view of a [`Solution`], so that the registry can keep all
+/// days in one table.
+pub trait DynSolution: Sync {
+  fn solve(&self, part: u8, input: &str) -> Result<String>;
+  fn flags(&self) -> &'static [Flag];
+  fn run(
+    &self,
+    input: &str,
+    flags: &Flags,
+    out: &mut dyn io::Write,
+  ) -> Result<()>;
+  fn bench(
+    &self,
+    input: &str,
+    settings: bench::Settings,
+  ) -> Result<bench::StageTimings>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+  fn solve(&self, part: u8, input: &str) -> Result<String> {
+    let input = S::parse(input)?;
+    match part {
+      1 => S::part1(&input).map(|answer| answer.to_string()),
+      2 => S::part2(&input).map(|answer| answer.to_string()),
+      _ => Err(AocError::Unsupported(format!(
+        "No part {}, puzzles have parts 1 and 2",
+        part
+      ))),
+    }
+  }
+
+  fn flags(&self) -> &'static [Flag] {
+    S::FLAGS
+  }
+
+  fn run(
+    &self,
+    input: &str,
+    flags: &Flags,
+    out: &mut dyn io::Write,
+  ) -> Result<()> {
+    S::run(input, flags, out)
+  }
+
+  fn bench(
+    &self,
+    input: &str,
+    settings: bench::Settings,
+  ) -> Result<bench::StageTimings> {
+    bench::bench::<S>(input, settings)
+  }
+}
@@ -0,0 +1,89 @@
+use adventofcode_2021::bitset::{BitGrid, BitSet};
+
+#[test]
+fn set_clear_and_toggle() {
+  let mut bits = BitSet::new(130);
+  for bit in [0, 63, 64, 129] {
+    bits.set(bit);
+  }
+  assert!(bits.get(63) && bits.get(64) && !bits.get(65));
+  assert_eq!(bits.count(), 4);
+
+  // Clearing a cleared bit leaves it cleared rather than toggling it.
+  bits.clear(64);
+  bits.clear(64);
+  assert!(!bits.get(64));
+
+  bits.toggle(1);
+  bits.toggle(129);
+  assert_eq!(bits.iter().collect::<Vec<_>>(), [0, 1, 63]);
+}
+
+#[test]
+fn full_and_empty() {
+  let mut bits = BitSet::new(65);
+  assert!(bits.is_empty() && !bits.is_full());
+  for bit in 0..65 {
+    bits.set(bit);
+  }
+  assert!(bits.is_full());
+  assert_eq!(bits.count(), 65);
+  assert!(BitSet::new(0).is_full());
+}
+
+#[test]
+fn union_and_intersection() {
+  let mut evens = BitSet::new(100);
+  let mut threes = BitSet::new(100);
+  (0..100).step_by(2).for_each(|bit| evens.set(bit));
+  (0..100).step_by(3).for_each(|bit| threes.set(bit));
+
+  let mut both = evens.clone();
+  both.intersect_with(&threes);
+  assert!(both.iter().eq((0..100).step_by(6)));
+
+  let mut either = evens;
+  either.union_with(&threes);
+  assert_eq!(either.count(), 50 + 34 - 17);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn bit_out_of_bounds() {
+  BitSet::new(64).set(64);
+}
+
+#[test]
+#[should_panic(expected = "capacities differ")]
+fn union_of_different_capacities() {
+  BitSet::new(64).union_with(&BitSet::new(65));
+}
+
+#[test]
+fn grid() {
+  let mut grid = BitGrid::new(3, 2);
+  grid.set((0, 2));
+  grid.set((1, 0));
+  grid.toggle((1, 1));
+  grid.toggle((0, 2));
+  assert_eq!(grid.iter().collect::<Vec<_>>(), [(1, 0), (1, 1)]);
+  assert_eq!(grid.count(), 2);
+  assert_eq!(grid.as_bit_set().iter().collect::<Vec<_>>(), [3, 4]);
+
+  let mut other = BitGrid::new(3, 2);
+  other.set((1, 1));
+  other.set((0, 0));
+  let mut both = grid.clone();
+  both.intersect_with(&other);
+  assert_eq!(both.iter().collect::<Vec<_>>(), [(1, 1)]);
+  grid.union_with(&other);
+  grid.clear((1, 0));
+  assert_eq!(grid.iter().collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn column_out_of_bounds() {
+  // Would be bit 3 of the set, but the row has only 3 columns.
+  BitGrid::new(3, 2).get((0, 3));
+}
@@ -0,0 +1,149 @@
+use adventofcode_2021::day11::{
+  animate, run_step, simulate, to_csv, Animation, Cycle, Day11, Map,
+};
+use adventofcode_2021::grid::Grid;
+use adventofcode_2021::registry;
+use adventofcode_2021::solution::Flags;
+use adventofcode_2021::{AocError, Solution};
+use std::time::Duration;
+
+#[test]
+fn small_grid() {
+  let mut map = Day11::parse("11111\n19991\n19191\n19991\n11111\n").unwrap();
+  assert_eq!(run_step(&mut map), 9);
+  assert_eq!(map.to_string(), "34543\n40004\n50005\n40004\n34543\n");
+  assert_eq!(run_step(&mut map), 0);
+  assert_eq!(map.to_string(), "45654\n51115\n61116\n51115\n45654\n");
+}
+
+#[test]
+fn non_square_grid() {
+  let mut map = Day11::parse("989\n").unwrap();
+  assert_eq!(run_step(&mut map), 3);
+  assert_eq!(map.to_string(), "000\n");
+  assert_eq!(Day11::part2(&Day11::parse("7\n8\n").unwrap()).unwrap(), 2);
+}
+
+#[test]
+fn grid_that_never_synchronizes() {
+  // The state comes back every 9 steps without all cells flashing at once.
+  match Day11::part2(&Day11::parse("20\n").unwrap()) {
+    Err(AocError::NoAnswer(message)) => {
+      assert_eq!(message, "never synchronizes")
+    }
+    other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn rows_of_different_width() {
+  match Day11::parse("123\n12\n123\n") {
+    Err(AocError::Parse(err)) => {
+      assert_eq!((err.line, err.expected.as_str()), (2, "a row of 3 digits"))
+    }
+    _ => panic!("expected a parse error"),
+  }
+}
+
+#[test]
+fn large_grid() {
+  let mut seed = 12345u32;
+  let mut map: Map = Grid::from_fn(200, 150, |_| {
+    seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+    (seed >> 16) as u8 % 10
+  });
+
+  for _ in 0..100 {
+    let flashes = run_step(&mut map);
+    assert!(flashes <= 200 * 150);
+    assert!(map.iter().all(|&energy| energy <= 9));
+    assert!(flashes == 0 || map.iter().any(|&energy| energy == 0));
+  }
+}
+
+#[test]
+fn animation_ends_on_synchronized_step() {
+  let map = Day11::parse("7\n8\n").unwrap();
+  let animation = Animation {
+    waves: false,
+    delay: Duration::ZERO,
+  };
+  let mut out = vec![];
+  assert_eq!(animate(&map, &animation, &mut out).unwrap(), Some(2));
+
+  let out = String::from_utf8(out).unwrap();
+  assert_eq!(out.matches("\x1b[H").count(), 3);
+  assert!(out.contains("step 2, 2 flashes in 2 waves"));
+  assert!(out.ends_with("synchronized after 2 steps\n"));
+}
+
+#[test]
+fn animation_of_a_grid_that_never_synchronizes() {
+  let map = Day11::parse("20\n").unwrap();
+  let animation = Animation {
+    waves: false,
+    delay: Duration::ZERO,
+  };
+  let mut out = vec![];
+  assert_eq!(animate(&map, &animation, &mut out).unwrap(), None);
+
+  let out = String::from_utf8(out).unwrap();
+  assert_eq!(out.matches("\x1b[H").count(), 10);
+  assert!(out.ends_with("never synchronizes, step 9 repeats an earlier one\n"));
+}
+
+#[test]
+fn animation_with_waves() {
+  let day = registry::find(11).unwrap();
+  let flags = Flags::new(vec![
+    ("--visualize", None),
+    ("--waves", None),
+    ("--delay", Some("0")),
+  ]);
+  let mut out = vec![];
+  day.run("7\n8\n", &flags, &mut out).unwrap();
+
+  let out = String::from_utf8(out).unwrap();
+  assert!(out.contains("step 2, wave 1\x1b[K\n\x1b[0;38;5;250m9\x1b[0m\n"));
+  assert!(out.contains("step 2, wave 2\x1b[K\n\x1b[1;93m*\x1b[0m\n"));
+  assert_eq!(out.matches("\x1b[H").count(), 5);
+}
+
+#[test]
+fn step_records() {
+  let map = Day11::parse("11111\n19991\n19191\n19991\n11111\n").unwrap();
+  let simulation = simulate(&map, 1);
+  let record = &simulation.records[0];
+  assert_eq!(
+    (record.step, record.flashes, record.cascade_depth),
+    (1, 9, 2)
+  );
+  assert_eq!(record.flashed[..3], [(1, 1), (1, 2), (1, 3)]);
+  assert_eq!(record.flashed[8], (2, 2));
+  assert_eq!(simulation.cycle, None);
+}
+
+#[test]
+fn cycle_after_synchronizing() {
+  let map = Day11::parse("7\n8\n").unwrap();
+  let simulation = simulate(&map, 100);
+  assert_eq!(simulation.synchronized(), Some(2));
+  assert_eq!(
+    simulation.cycle,
+    Some(Cycle {
+      start: 2,
+      period: 10
+    })
+  );
+  assert_eq!(simulation.records.len(), 12);
+}
+
+#[test]
+fn csv() {
+  let map = Day11::parse("7\n8\n").unwrap();
+  let simulation = simulate(&map, 2);
+  assert_eq!(
+    to_csv(&simulation.records),
+    "step,flashes,cascade_depth,flashed\n1,0,0,\n2,2,2,1:0 0:0\n"
+  );
+}
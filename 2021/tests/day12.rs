@@ -0,0 +1,315 @@
+use adventofcode_2021::day12::{
+  find_paths, paths, Cave, CaveGraph, Day12, VisitPolicy,
+};
+use adventofcode_2021::solution::Flags;
+use adventofcode_2021::Solution;
+use std::fs;
+
+const EXAMPLE_PATHS: &str = "\
+start,A,b,A,c,A,end
+start,A,b,A,end
+start,A,b,end
+start,A,c,A,b,A,end
+start,A,c,A,b,end
+start,A,c,A,end
+start,A,end
+start,b,A,c,A,end
+start,b,A,end
+start,b,end
+";
+
+fn example() -> String {
+  fs::read_to_string("data/day-12-example").unwrap()
+}
+
+#[test]
+fn paths_in_order() {
+  let cave_system = Day12::parse(&example()).unwrap();
+  let printed: String = paths(&cave_system, &VisitPolicy::new(0))
+    .unwrap()
+    .map(|path| format!("{}\n", path))
+    .collect();
+  assert_eq!(printed, EXAMPLE_PATHS);
+
+  let path = paths(&cave_system, &VisitPolicy::new(0))
+    .unwrap()
+    .next()
+    .unwrap();
+  let names: Vec<_> = path.caves().iter().map(Cave::name).collect();
+  assert_eq!(names, ["start", "A", "b", "A", "c", "A", "end"]);
+}
+
+#[test]
+fn paths_with_a_repeat() {
+  let cave_system = Day12::parse(&example()).unwrap();
+  let all: Vec<_> = paths(&cave_system, &VisitPolicy::new(1))
+    .unwrap()
+    .map(|p| p.to_string())
+    .collect();
+  assert_eq!(all.len(), 36);
+  assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+  assert!(all.contains(&"start,A,b,A,b,A,c,A,end".to_string()));
+}
+
+#[test]
+fn filtered_paths() {
+  let cave_system = Day12::parse(&example()).unwrap();
+  let c = Cave::new("c");
+  let policy = VisitPolicy::new(0);
+  let through_c = paths(&cave_system, &policy)
+    .unwrap()
+    .filter(|path| path.visits(&c));
+  assert_eq!(through_c.count(), 5);
+}
+
+#[test]
+fn run_prints_paths() {
+  let mut out = vec![];
+  let flags = Flags::new(vec![("--paths", None), ("--through", Some("d"))]);
+  Day12::run(&example(), &flags, &mut out).unwrap();
+  assert_eq!(String::from_utf8(out).unwrap(), "");
+
+  let mut out = vec![];
+  let flags = Flags::new(vec![
+    ("--paths", None),
+    ("--repeats", Some("1")),
+    ("--through", Some("d")),
+  ]);
+  Day12::run(&example(), &flags, &mut out).unwrap();
+  assert_eq!(
+    String::from_utf8(out).unwrap(),
+    "start,A,b,d,b,A,c,A,end\n\
+     start,A,b,d,b,A,end\n\
+     start,A,b,d,b,end\n\
+     start,A,c,A,b,d,b,A,end\n\
+     start,A,c,A,b,d,b,end\n\
+     start,b,d,b,A,c,A,end\n\
+     start,b,d,b,A,end\n\
+     start,b,d,b,end\n"
+  );
+}
+
+/// Small caves in a row, each pair connected directly and through a big
+/// cave of its own.
+fn ladder(small_caves: usize) -> String {
+  // Names are letters only, `xa` to `xz`, then `xba` and so on.
+  let name = |mut i: usize| {
+    let mut name = vec![];
+    loop {
+      name.push(b'a' + (i % 26) as u8);
+      i /= 26;
+      if i == 0 {
+        break;
+      }
+    }
+    name.push(b'x');
+    name.reverse();
+    String::from_utf8(name).unwrap()
+  };
+
+  let mut input = format!("start-{}\n", name(0));
+  for i in 1..small_caves {
+    let (previous, small, big) = (name(i - 1), name(i), name(i).to_uppercase());
+    input += &format!(
+      "{}-{}\n{}-{}\n{}-{}\n",
+      previous, small, previous, big, big, small
+    );
+  }
+  input + &format!("{}-end\n", name(small_caves - 1))
+}
+
+#[test]
+fn counts_match_listed_paths() {
+  for (input, end) in [(example(), "d"), (ladder(6), "xd")] {
+    let cave_system = Day12::parse(&input).unwrap();
+    let graph = CaveGraph::new(&cave_system).unwrap();
+    for policy in policies(end) {
+      let listed = find_paths(&cave_system, &policy).unwrap() as u64;
+      assert_eq!(graph.count_paths(&policy).unwrap(), listed, "{:?}", policy);
+    }
+  }
+}
+
+fn policies(end: &str) -> Vec<VisitPolicy> {
+  let mut policies: Vec<_> = (0..4).map(VisitPolicy::new).collect();
+  let mut policy = VisitPolicy::new(1);
+  policy.limits.insert(Cave::new("A"), 3);
+  policy.limits.insert(Cave::new("b"), 3);
+  policy.limits.insert(Cave::new("xb"), 2);
+  policies.push(policy.clone());
+  policy.limits = [(Cave::new("c"), 0), (Cave::new("xc"), 0)].into();
+  policies.push(policy.clone());
+  policy.end = Cave::new(end);
+  policies.push(policy);
+  policies
+}
+
+fn listed(cave_system_input: &str, policy: &VisitPolicy) -> Vec<String> {
+  let cave_system = Day12::parse(cave_system_input).unwrap();
+  paths(&cave_system, policy)
+    .unwrap()
+    .map(|path| path.to_string())
+    .collect()
+}
+
+#[test]
+fn visit_limits() {
+  let mut policy = VisitPolicy::new(0);
+  policy.limits.insert(Cave::new("A"), 1);
+  assert_eq!(
+    listed(&example(), &policy),
+    [
+      "start,A,b,end",
+      "start,A,end",
+      "start,b,A,end",
+      "start,b,end"
+    ]
+  );
+
+  policy.limits = [(Cave::new("A"), 0)].into();
+  assert_eq!(listed(&example(), &policy), ["start,b,end"]);
+
+  // Only b may be visited twice, however many repeats are left.
+  policy = VisitPolicy::new(3);
+  let limits = [("b", 2), ("c", 1), ("d", 1)];
+  policy.limits = limits.map(|(cave, n)| (Cave::new(cave), n)).into();
+  let paths = listed(&example(), &policy);
+  // The 36 paths of part 2 but the 6 visiting c twice.
+  assert_eq!(paths.len(), 36 - 6);
+  assert!(paths.iter().all(|path| path.matches('c').count() <= 1));
+}
+
+#[test]
+fn distinct_caves_twice() {
+  let twice = |repeats| listed(&example(), &VisitPolicy::new(repeats));
+  let (once, one, two) = (twice(0), twice(1), twice(2));
+  assert_eq!((once.len(), one.len()), (10, 36));
+  assert!(one.iter().all(|path| two.contains(path)));
+  assert!(two.contains(&"start,A,b,A,b,A,c,A,c,A,end".to_string()));
+  assert!(!two.iter().any(|path| path.matches(",b,").count() > 2));
+}
+
+#[test]
+fn custom_end() {
+  let mut policy = VisitPolicy::new(0);
+  policy.end = Cave::new("d");
+  let paths = listed(&example(), &policy);
+  assert!(paths.iter().all(|path| path.ends_with(",d")));
+  // The end cave is an ordinary small cave then.
+  assert!(paths.contains(&"start,A,end,b,d".to_string()));
+  assert!(paths.contains(&"start,b,d".to_string()));
+
+  let cave_system = Day12::parse(&example()).unwrap();
+  let graph = CaveGraph::new(&cave_system).unwrap();
+  assert_eq!(graph.count_paths(&policy).unwrap(), paths.len() as u64);
+}
+
+#[test]
+fn counts_in_large_graphs() {
+  let cave_system = Day12::parse(&ladder(40)).unwrap();
+  let graph = CaveGraph::new(&cave_system).unwrap();
+  assert_eq!(graph.count_paths(&VisitPolicy::new(0)).unwrap(), 1 << 39);
+  assert!(graph.count_paths(&VisitPolicy::new(2)).unwrap() > 1 << 39);
+}
+
+#[test]
+fn uncountable_graphs() {
+  let count = |input: &str, policy: &VisitPolicy| {
+    let cave_system = Day12::parse(input).unwrap();
+    CaveGraph::new(&cave_system).unwrap().count_paths(policy)
+  };
+  let connected_big_caves = "start-A\nA-B\nB-end\n";
+  let mut policy = VisitPolicy::new(0);
+  assert!(count(connected_big_caves, &policy).is_err());
+  policy.limits.insert(Cave::new("A"), 2);
+  let listed = listed(connected_big_caves, &policy);
+  assert_eq!(listed, ["start,A,B,A,B,end", "start,A,B,end"]);
+  assert_eq!(count(connected_big_caves, &policy).unwrap(), 2);
+
+  assert!(count("start-A\nA-b\n", &VisitPolicy::new(0)).is_err());
+
+  let too_many = Day12::parse(&ladder(65)).unwrap();
+  assert!(CaveGraph::new(&too_many).is_err());
+}
+
+#[test]
+fn endless_paths() {
+  let connected_big_caves = "start-A\nA-B\nB-end\n";
+  let cave_system = Day12::parse(connected_big_caves).unwrap();
+  assert!(paths(&cave_system, &VisitPolicy::new(0)).is_err());
+  assert!(find_paths(&cave_system, &VisitPolicy::new(0)).is_err());
+
+  let flags = Flags::new(vec![("--paths", None)]);
+  let mut out = vec![];
+  assert!(Day12::run(connected_big_caves, &flags, &mut out).is_err());
+  assert!(out.is_empty());
+}
+
+#[test]
+fn big_end_next_to_a_big_cave() {
+  let input = "start-A\nA-B\nB-end\n";
+  let mut policy = VisitPolicy::new(0);
+  policy.end = Cave::new("B");
+  assert_eq!(listed(input, &policy), ["start,A,B"]);
+  let cave_system = Day12::parse(input).unwrap();
+  let graph = CaveGraph::new(&cave_system).unwrap();
+  assert_eq!(graph.count_paths(&policy).unwrap(), 1);
+
+  // Freeing end again lets paths go back and forth between A and B.
+  policy.limits.insert(Cave::new("end"), 5);
+  policy.end = Cave::new("end");
+  assert!(paths(&cave_system, &policy).is_err());
+}
+
+#[test]
+fn count_through_a_cave() {
+  let cave_system = Day12::parse(&example()).unwrap();
+  let graph = CaveGraph::new(&cave_system).unwrap();
+  for repeats in 0..3 {
+    let policy = VisitPolicy::new(repeats);
+    for cave in ["start", "A", "b", "c", "d", "end", "x"].map(Cave::new) {
+      let listed = paths(&cave_system, &policy)
+        .unwrap()
+        .filter(|path| path.visits(&cave))
+        .count() as u64;
+      let counted = graph.count_paths_through(&policy, &cave).unwrap();
+      assert_eq!(counted, listed, "{} {:?}", repeats, cave);
+    }
+  }
+
+  let mut out = vec![];
+  let flags = Flags::new(vec![
+    ("--count", None),
+    ("--repeats", Some("1")),
+    ("--through", Some("d")),
+  ]);
+  Day12::run(&example(), &flags, &mut out).unwrap();
+  assert_eq!(String::from_utf8(out).unwrap(), "8\n");
+}
+
+#[test]
+fn run_with_a_policy() {
+  let mut out = vec![];
+  let flags = Flags::new(vec![
+    ("--count", None),
+    ("--repeats", Some("1")),
+    ("--forbid", Some("c,d")),
+  ]);
+  Day12::run(&example(), &flags, &mut out).unwrap();
+  assert_eq!(String::from_utf8(out).unwrap(), "9\n");
+
+  let mut out = vec![];
+  let flags = Flags::new(vec![
+    ("--paths", None),
+    ("--limit", Some("A=1")),
+    ("--end", Some("c")),
+  ]);
+  Day12::run(&example(), &flags, &mut out).unwrap();
+  assert_eq!(
+    String::from_utf8(out).unwrap(),
+    "start,A,c\nstart,b,A,c\nstart,b,end,A,c\n"
+  );
+
+  let flags = Flags::new(vec![("--count", None), ("--limit", Some("A"))]);
+  assert!(Day12::run(&example(), &flags, &mut vec![]).is_err());
+}
@@ -0,0 +1,206 @@
+use adventofcode_2021::day13::{
+  apply_fold, apply_fold_with, dot_grid, render_pbm, replay, replay_with,
+  Bounds, Day13, Dot, DotsAndFolds, Fold, OnFoldLine, Sheet,
+};
+use adventofcode_2021::solution::Flags;
+use adventofcode_2021::AocError;
+use adventofcode_2021::Solution;
+use std::collections::HashSet;
+use std::fs;
+
+fn example() -> DotsAndFolds {
+  Day13::parse(&fs::read_to_string("data/day-13-example").unwrap()).unwrap()
+}
+
+#[test]
+fn replay_stages() {
+  let stages: Vec<_> = replay(&example()).map(Result::unwrap).collect();
+  let visible: Vec<_> = stages.iter().map(|stage| stage.visible()).collect();
+  assert_eq!(visible, [18, 17, 16]);
+
+  let bounds = |x, y| Bounds {
+    top_left: Dot { x: 0, y: 0 },
+    bottom_right: Dot { x, y },
+  };
+  assert_eq!(stages[0].bounds(), Some(bounds(10, 14)));
+  assert_eq!(stages[1].bounds(), Some(bounds(10, 4)));
+  assert_eq!(stages[2].bounds(), Some(bounds(4, 4)));
+  assert!(matches!(stages[2].fold, Some(Fold::X(5))));
+  assert!(stages[2].dots.contains(&Dot { x: 4, y: 4 }));
+}
+
+fn dots(xs_and_ys: &[(u32, u32)]) -> Vec<Dot> {
+  xs_and_ys.iter().map(|&(x, y)| Dot { x, y }).collect()
+}
+
+#[test]
+fn fold_left_of_the_middle() {
+  let mut folded = dots(&[(0, 0), (2, 0), (5, 1)]);
+  apply_fold(Fold::X(1), &mut folded).unwrap();
+  // The folded part is 4 columns wide, so the one column left of the line
+  // lands on its last column.
+  assert_eq!(folded, dots(&[(3, 0), (3, 0), (0, 1)]));
+}
+
+#[test]
+fn fold_above_the_middle() {
+  let mut folded = dots(&[(0, 0), (0, 1), (1, 3), (2, 6)]);
+  apply_fold(Fold::Y(2), &mut folded).unwrap();
+  assert_eq!(folded, dots(&[(0, 2), (0, 3), (1, 3), (2, 0)]));
+}
+
+#[test]
+fn dots_on_the_fold_line() {
+  let mut folded = dots(&[(0, 0), (1, 3), (4, 6)]);
+  apply_fold(Fold::Y(3), &mut folded).unwrap();
+  assert_eq!(folded, dots(&[(0, 0), (4, 0)]));
+
+  let mut folded = dots(&[(0, 0), (1, 3), (4, 6)]);
+  match apply_fold_with(Fold::Y(3), &mut folded, OnFoldLine::Reject) {
+    Err(AocError::NoAnswer(message)) => {
+      assert_eq!(message, "dot 1,3 lies on the line of fold along y=3")
+    }
+    other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn strict_parse_points_at_the_dot() {
+  let input = "2,0\n5,0\n\nfold along x=4\nfold along x=3\nfold along y=1\n";
+  let error = DotsAndFolds::parse_strict(input).unwrap_err();
+  assert_eq!((error.line, error.column), (2, 1));
+  assert_eq!(error.token, "5,0");
+  assert_eq!(
+    error.expected,
+    "a dot off the line of fold along x=3 (line 5)"
+  );
+  assert!(DotsAndFolds::parse_strict("2,0\n5,0\n\nfold along x=4\n").is_ok());
+
+  let flags = Flags::new(vec![("--strict", None)]);
+  match Day13::run(input, &flags, &mut vec![]) {
+    Err(AocError::Parse(error)) => assert_eq!(error.line, 2),
+    other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn strict_replay_stops_at_a_dot_on_a_fold_line() {
+  let input = "2,0\n5,0\n\nfold along x=4\nfold along x=3\nfold along y=1\n";
+  let input = Day13::parse(input).unwrap();
+  let stages: Vec<_> = replay_with(&input, OnFoldLine::Reject).collect();
+  assert_eq!(stages.len(), 3);
+  assert!(stages[1].is_ok() && stages[2].is_err());
+  assert_eq!(replay(&input).count(), 4);
+}
+
+#[test]
+fn pbm() {
+  let dots = [Dot { x: 0, y: 0 }, Dot { x: 2, y: 1 }];
+  assert_eq!(
+    render_pbm(&dot_grid(dots.iter())),
+    "P1\n3 2\n1 0 0\n0 0 1\n"
+  );
+}
+
+#[test]
+fn run_replays_folds() {
+  let input = fs::read_to_string("data/day-13-example").unwrap();
+  let mut out = vec![];
+  Day13::run(&input, &Flags::new(vec![("--replay", None)]), &mut out).unwrap();
+  let out = String::from_utf8(out).unwrap();
+  let headers: Vec<_> = out.lines().filter(|line| line.contains(':')).collect();
+  assert_eq!(
+    headers,
+    [
+      "before folding: 18 dots from 0,0 to 10,14",
+      "fold along y=7: 17 dots from 0,0 to 10,4",
+      "fold along x=5: 16 dots from 0,0 to 4,4",
+    ]
+  );
+  assert!(out.ends_with("#####\n#...#\n#...#\n#...#\n#####\n\n"));
+}
+
+#[test]
+fn sheets_hold_each_dot_once() {
+  let sheet = Sheet::new(dots(&[(1, 0), (3, 0), (1, 0)]));
+  assert_eq!(sheet.len(), 2);
+  let folded = sheet.fold(Fold::X(2), OnFoldLine::Drop).unwrap();
+  assert_eq!(folded.len(), 1);
+  assert!(folded.contains(&Dot { x: 1, y: 0 }));
+}
+
+#[test]
+fn sheet_representation_follows_density() {
+  let sparse = Sheet::new(dots(&[(0, 0), (1000, 1000)]));
+  assert!(matches!(sparse, Sheet::Sparse(_)));
+  let folded = sparse.fold(Fold::X(500), OnFoldLine::Drop).unwrap();
+  assert!(matches!(folded, Sheet::Sparse(_)));
+  let folded = folded.fold(Fold::Y(500), OnFoldLine::Drop).unwrap();
+  assert!(matches!(folded, Sheet::Dense(_)));
+  assert_eq!(folded.len(), 1);
+
+  let dense = Sheet::new(dots(&[(0, 0), (7, 7)]));
+  assert!(matches!(dense, Sheet::Dense(_)));
+  assert_eq!(dense.iter().collect::<Vec<_>>(), dots(&[(0, 0), (7, 7)]));
+  assert!(!dense.contains(&Dot { x: 8, y: 0 }));
+}
+
+#[test]
+fn large_sheets_fold_like_dot_lists() {
+  // A linear congruential generator, for dots that look random.
+  let mut state = 12345u64;
+  let mut next = |bound: u64| {
+    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+    ((state >> 33) % bound) as u32
+  };
+  let mut list: Vec<Dot> = (0..200_000)
+    .map(|_| Dot {
+      x: next(40001),
+      y: next(30001),
+    })
+    .collect();
+  let mut sheet = Sheet::new(list.iter().copied());
+  assert!(matches!(sheet, Sheet::Sparse(_)));
+
+  let folds = [
+    Fold::X(20000),
+    Fold::Y(15000),
+    Fold::X(10000),
+    Fold::Y(7500),
+    Fold::X(5000),
+    Fold::Y(3750),
+    Fold::X(2500),
+  ];
+  for fold in folds {
+    apply_fold(fold, &mut list).unwrap();
+    sheet = sheet.fold(fold, OnFoldLine::Drop).unwrap();
+    let expected: HashSet<Dot> = list.iter().copied().collect();
+    assert_eq!(sheet.len(), expected.len());
+    assert!(expected.iter().all(|dot| sheet.contains(dot)));
+  }
+  assert!(matches!(sheet, Sheet::Dense(_)));
+}
+
+#[test]
+fn strict_run_on_a_sparse_sheet() {
+  let input = "0,0\n200000,200000\n\nfold along y=100001\n";
+  let mut out = vec![];
+  Day13::run(input, &Flags::new(vec![("--strict", None)]), &mut out).unwrap();
+  assert_eq!(
+    String::from_utf8(out).unwrap(),
+    "before folding: 2 dots from 0,0 to 200000,200000\n\
+     fold along y=100001: 2 dots from 0,0 to 200000,2\n"
+  );
+}
+
+#[test]
+fn huge_sheets_are_not_drawn() {
+  let sheet = Sheet::new(dots(&[(0, 0), (200000, 200000)]));
+  assert!(matches!(sheet.to_grid(), Err(AocError::Unsupported(_))));
+  let sheet = Sheet::new(dots(&[(0, 0), (4095, 4095)]));
+  assert_eq!(sheet.to_grid().unwrap().width(), 4096);
+
+  let input = "0,0\n200000,200000\n\nfold along y=100001\n";
+  let flags = Flags::new(vec![("--replay", None)]);
+  assert!(Day13::run(input, &flags, &mut vec![]).is_err());
+}
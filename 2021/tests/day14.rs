@@ -0,0 +1,134 @@
+use adventofcode_2021::day14::{
+  element_counts_after, matrix_difference, polymer_difference,
+  transition_matrix, Arithmetic, Day14, Polymer,
+};
+use adventofcode_2021::solution::Flags;
+use adventofcode_2021::{AocError, Solution};
+use std::fs;
+
+fn example() -> Polymer {
+  Day14::parse(&fs::read_to_string("data/day-14-example").unwrap()).unwrap()
+}
+
+#[test]
+fn matrix_matches_steps() {
+  let polymer = example();
+  for steps in [0, 1, 2, 10, 40] {
+    assert_eq!(
+      matrix_difference(&polymer, steps).unwrap(),
+      polymer_difference(&polymer, steps as usize) as u128,
+      "{}",
+      steps
+    );
+  }
+  assert_eq!(matrix_difference(&polymer, 10).unwrap(), 1588);
+
+  let counts = element_counts_after(&polymer, 10, Arithmetic::Exact).unwrap();
+  let counts: Vec<_> = counts.into_iter().collect();
+  assert_eq!(
+    counts,
+    [(b'B', 1749), (b'C', 298), (b'H', 161), (b'N', 865)]
+  );
+}
+
+#[test]
+fn transitions() {
+  let polymer = Day14::parse("AB\n\nAA -> B\nAB -> A\nBA -> A\nBB -> B\n");
+  let (pairs, matrix) = transition_matrix(&polymer.unwrap().rules).unwrap();
+  let pairs: Vec<_> = pairs.iter().map(|&(a, b)| [a, b]).collect();
+  assert_eq!(pairs, [*b"AA", *b"AB", *b"BA", *b"BB"]);
+  // AA yields AB and BA, BB yields BB twice.
+  let rows: Vec<_> = matrix.rows().collect();
+  assert_eq!(
+    rows,
+    [[0, 1, 1, 0], [1, 1, 0, 0], [1, 0, 1, 0], [0, 0, 0, 2]]
+  );
+}
+
+#[test]
+fn incomplete_rules() {
+  let mut polymer = example();
+  polymer.rules.remove(&(b'C', b'B'));
+  match transition_matrix(&polymer.rules) {
+    Err(AocError::NoAnswer(message)) => assert_eq!(message, "no rule for CB"),
+    other => panic!("{:?}", other),
+  }
+  assert!(matrix_difference(&polymer, 10).is_err());
+  polymer.rules.clear();
+  assert!(transition_matrix(&polymer.rules).is_err());
+}
+
+fn pow_mod(base: u128, mut exponent: u64, modulus: u128) -> u128 {
+  let (mut base, mut result) = (base % modulus, 1 % modulus);
+  while exponent > 0 {
+    if exponent & 1 == 1 {
+      result = result * base % modulus;
+    }
+    base = base * base % modulus;
+    exponent >>= 1;
+  }
+  result
+}
+
+#[test]
+fn modular_counts() {
+  let polymer = example();
+  let exact = element_counts_after(&polymer, 40, Arithmetic::Exact).unwrap();
+  let modulus = 1_000_003;
+  let reduced =
+    element_counts_after(&polymer, 40, Arithmetic::Modulo(modulus)).unwrap();
+  for (element, count) in exact {
+    assert_eq!(reduced[&element], count % u128::from(modulus));
+  }
+
+  // NNCB grows to 3 * 2^n + 1 elements in n steps.
+  let modulus = 1_000_000_007;
+  let steps = 1_000_000_000_000;
+  let counts =
+    element_counts_after(&polymer, steps, Arithmetic::Modulo(modulus)).unwrap();
+  let modulus = u128::from(modulus);
+  let total = counts.values().sum::<u128>() % modulus;
+  assert_eq!(total, (3 * pow_mod(2, steps, modulus) + 1) % modulus);
+}
+
+#[test]
+fn overflow() {
+  let polymer = example();
+  assert!(matrix_difference(&polymer, 120).is_ok());
+  match matrix_difference(&polymer, 1_000_000_000_000) {
+    Err(AocError::NoAnswer(message)) => {
+      assert_eq!(message, "counts overflow u128 within 1000000000000 steps")
+    }
+    other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn run_with_steps() {
+  let input = fs::read_to_string("data/day-14-example").unwrap();
+  let mut out = vec![];
+  let flags = Flags::new(vec![("--steps", Some("40"))]);
+  Day14::run(&input, &flags, &mut out).unwrap();
+  assert_eq!(String::from_utf8(out).unwrap(), "2188189693529\n");
+
+  let mut out = vec![];
+  let flags =
+    Flags::new(vec![("--steps", Some("10")), ("--modulus", Some("7"))]);
+  Day14::run(&input, &flags, &mut out).unwrap();
+  assert_eq!(String::from_utf8(out).unwrap(), "B 6\nC 4\nH 0\nN 4\n");
+
+  let flags = Flags::new(vec![("--steps", Some("1000000000000"))]);
+  match Day14::run(&input, &flags, &mut vec![]) {
+    Err(AocError::NoAnswer(message)) => assert_eq!(
+      message,
+      "counts overflow u128 within 1000000000000 steps, try --modulus"
+    ),
+    other => panic!("{:?}", other),
+  }
+
+  let flags = Flags::new(vec![("--modulus", Some("7"))]);
+  assert!(Day14::run(&input, &flags, &mut vec![]).is_err());
+  let flags =
+    Flags::new(vec![("--steps", Some("1")), ("--modulus", Some("0"))]);
+  assert!(Day14::run(&input, &flags, &mut vec![]).is_err());
+}
@@ -0,0 +1,247 @@
+use adventofcode_2021::day15::{
+  increase_risk, lowest_risk_route, render_ansi, render_ppm, search,
+  search_with, Day15, Heuristic, Map, Moves, RiskMap, Route, Rules, Size,
+  TiledMap,
+};
+use adventofcode_2021::grid::Grid;
+use adventofcode_2021::Solution;
+use std::fs;
+
+fn example() -> RiskMap {
+  Day15::parse(&fs::read_to_string("data/day-15-example").unwrap()).unwrap()
+}
+
+fn assert_valid_route<M: Map<u32>>(risk_map: &M, route: &Route) {
+  assert_eq!(route.path.first(), Some(&risk_map.start()));
+  assert_eq!(route.path.last(), Some(&risk_map.end()));
+  for pair in route.path.windows(2) {
+    let ((r1, c1), (r2, c2)) = (pair[0], pair[1]);
+    assert_eq!(r1.abs_diff(r2) + c1.abs_diff(c2), 1, "{:?}", pair);
+  }
+  let risk: u32 = route.path[1..].iter().map(|&f| risk_map.get(f)).sum();
+  assert_eq!(risk, route.risk);
+}
+
+fn assert_follows_rules<M: Map<u32>>(
+  risk_map: &M,
+  rules: &Rules,
+  route: &Route,
+) {
+  assert_eq!(route.path.first(), Some(&rules.start));
+  assert_eq!(route.path.last(), Some(&rules.end));
+  for pair in route.path.windows(2) {
+    let ((r1, c1), (r2, c2)) = (pair[0], pair[1]);
+    let offset = (r2 as isize - r1 as isize, c2 as isize - c1 as isize);
+    assert!(rules.moves.offsets().contains(&offset), "{:?}", pair);
+  }
+  assert!(route.path.iter().all(|f| !rules.impassable.contains(f)));
+  let risk: u32 = route.path[1..].iter().map(|&f| risk_map.get(f)).sum();
+  assert_eq!(risk, route.risk);
+}
+
+#[test]
+fn route() {
+  let risk_map = example();
+  let route = lowest_risk_route(&risk_map).unwrap();
+  assert_eq!(route.risk, 40);
+  assert_valid_route(&risk_map, &route);
+}
+
+#[test]
+fn tiled_route() {
+  let risk_map = example();
+  let tiled = TiledMap::new(5, &risk_map);
+  let route = lowest_risk_route(&tiled).unwrap();
+  assert_eq!(route.risk, 315);
+  assert_valid_route(&tiled, &route);
+}
+
+#[test]
+fn tiles_across_and_down() {
+  let risk_map = Day15::parse("19\n78\n").unwrap();
+  let tiles = Size {
+    width: 3,
+    height: 1,
+  };
+  let tiled = TiledMap::with_transform(&risk_map, tiles, increase_risk);
+  assert_eq!(
+    tiled.size(),
+    Size {
+      width: 6,
+      height: 2
+    }
+  );
+  let rows: Vec<Vec<u32>> = (0..2)
+    .map(|row| (0..6).map(|col| tiled.get((row, col))).collect())
+    .collect();
+  assert_eq!(rows, [[1, 9, 2, 1, 3, 2], [7, 8, 8, 9, 9, 1]]);
+}
+
+#[test]
+fn custom_tile_transform() {
+  let risk_map = Day15::parse("12\n34\n").unwrap();
+  let tiles = Size {
+    width: 2,
+    height: 2,
+  };
+  // Every other tile mirrored left to right, the ones below doubled.
+  let mirrored = |map: &RiskMap, (tile_row, tile_col), (row, col)| {
+    let col = if tile_col % 2 == 1 { 1 - col } else { col };
+    u32::from(map[(row, col)]) * (tile_row as u32 + 1)
+  };
+  let tiled = TiledMap::with_transform(&risk_map, tiles, mirrored);
+  let rows: Vec<Vec<u32>> = (0..4)
+    .map(|row| (0..4).map(|col| tiled.get((row, col))).collect())
+    .collect();
+  assert_eq!(
+    rows,
+    [[1, 2, 2, 1], [3, 4, 4, 3], [2, 4, 4, 2], [6, 8, 8, 6]]
+  );
+  let route = lowest_risk_route(&tiled).unwrap();
+  assert_eq!(route.risk, 2 + 2 + 1 + 3 + 2 + 6);
+  assert_valid_route(&tiled, &route);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn tiled_field_out_of_bounds() {
+  let risk_map = example();
+  TiledMap::new(2, &risk_map).get((0, 20));
+}
+
+#[test]
+fn single_field() {
+  let risk_map = Day15::parse("5\n").unwrap();
+  let route = lowest_risk_route(&risk_map).unwrap();
+  assert_eq!((route.risk, route.path), (0, vec![(0, 0)]));
+}
+
+#[test]
+fn ansi() {
+  let risk_map = Day15::parse("19\n11\n").unwrap();
+  let rendered = render_ansi(&risk_map, &lowest_risk_route(&risk_map).unwrap());
+  assert_eq!(
+    rendered,
+    "\x1b[1;97;41m1\x1b[0m\x1b[2m9\x1b[0m\n\
+     \x1b[1;97;41m1\x1b[0m\x1b[1;97;41m1\x1b[0m\n\
+     risk 2 over 3 fields\n"
+  );
+}
+
+#[test]
+fn ppm() {
+  let risk_map = Day15::parse("19\n11\n").unwrap();
+  let route = lowest_risk_route(&risk_map).unwrap();
+  let image = render_ppm(&risk_map, &route.path);
+  let header = b"P6\n2 2\n255\n";
+  assert_eq!(&image[..header.len()], header);
+  assert_eq!(
+    image[header.len()..],
+    [255, 0, 0, 30, 30, 30, 255, 0, 0, 255, 0, 0]
+  );
+}
+
+#[test]
+fn heuristics_find_the_lowest_risk() {
+  let risk_map = example();
+  let tiled = TiledMap::new(5, &risk_map);
+  let remaining_distance = |(row, col): (usize, usize)| (98 - row - col) as u32;
+
+  for heuristic in [
+    Heuristic::Zero,
+    Heuristic::Manhattan,
+    Heuristic::Custom(&|_| 0),
+  ] {
+    let search = search(&tiled, &heuristic).unwrap();
+    assert_eq!(search.route.risk, 315);
+    assert_valid_route(&tiled, &search.route);
+  }
+
+  // Overestimating the risk left makes the search head straight for the end,
+  // expanding far fewer fields but missing the lowest risk.
+  let exact = search(&tiled, &Heuristic::Zero).unwrap();
+  let greedy = |f| 9 * remaining_distance(f);
+  let greedy = search(&tiled, &Heuristic::Custom(&greedy)).unwrap();
+  assert_eq!(greedy.route.risk, 372);
+  assert!(greedy.expanded < exact.expanded / 10, "{}", greedy.expanded);
+  assert_valid_route(&tiled, &greedy.route);
+}
+
+#[test]
+fn manhattan_expands_fewer_fields() {
+  let risk_map = Grid::from_fn(20, 20, |_| 1u8);
+  let zero = search(&risk_map, &Heuristic::Zero).unwrap();
+  let manhattan = search(&risk_map, &Heuristic::Manhattan).unwrap();
+  assert_eq!((zero.route.risk, manhattan.route.risk), (38, 38));
+  assert_eq!(zero.expanded, 399);
+  assert!(manhattan.expanded < 100, "{}", manhattan.expanded);
+}
+
+#[test]
+fn start_and_end() {
+  let risk_map = example();
+  let mut rules = Rules::for_map(&risk_map);
+  rules.start = (9, 9);
+  rules.end = (0, 0);
+  let search = search_with(&risk_map, &rules, &Heuristic::Manhattan).unwrap();
+  // Both corners have risk 1, so the reversed route costs the same.
+  assert_eq!(search.route.risk, 40);
+  assert_follows_rules(&risk_map, &rules, &search.route);
+
+  rules.end = (9, 10);
+  assert!(search_with(&risk_map, &rules, &Heuristic::Zero).is_none());
+}
+
+#[test]
+fn diagonal_and_knight_moves() {
+  let risk_map = Grid::from_fn(20, 20, |_| 1u8);
+  let mut rules = Rules::for_map(&risk_map);
+  for (moves, risk) in
+    [(Moves::Four, 38), (Moves::Eight, 19), (Moves::Knight, 14)]
+  {
+    rules.moves = moves;
+    let search = search_with(&risk_map, &rules, &Heuristic::Zero).unwrap();
+    assert_eq!(search.route.risk, risk, "{:?}", moves);
+    assert_follows_rules(&risk_map, &rules, &search.route);
+  }
+
+  // A knight cannot move at all on a 2x2 map.
+  let risk_map = Grid::from_fn(2, 2, |_| 1u8);
+  rules = Rules::for_map(&risk_map);
+  rules.moves = Moves::Knight;
+  assert!(search_with(&risk_map, &rules, &Heuristic::Zero).is_none());
+}
+
+#[test]
+fn impassable_fields() {
+  let risk_map = Day15::parse("19\n11\n").unwrap();
+  let mut rules = Rules::for_map(&risk_map);
+  rules.impassable.insert((1, 0));
+  let route = search_with(&risk_map, &rules, &Heuristic::Zero)
+    .unwrap()
+    .route;
+  assert_eq!(route.path, [(0, 0), (0, 1), (1, 1)]);
+  assert_follows_rules(&risk_map, &rules, &route);
+
+  rules.impassable.insert((0, 1));
+  assert!(search_with(&risk_map, &rules, &Heuristic::Zero).is_none());
+  rules.impassable = [(1, 1)].into();
+  assert!(search_with(&risk_map, &rules, &Heuristic::Zero).is_none());
+}
+
+#[test]
+fn manhattan_stays_optimal_for_every_move() {
+  let risk_map = example();
+  let tiled = TiledMap::new(2, &risk_map);
+  let mut rules = Rules::for_map(&tiled);
+  rules.start = (3, 17);
+  rules.end = (15, 2);
+  rules.impassable = [(4, 16), (5, 5), (10, 10)].into();
+  for moves in [Moves::Four, Moves::Eight, Moves::Knight] {
+    rules.moves = moves;
+    let zero = search_with(&tiled, &rules, &Heuristic::Zero).unwrap();
+    let manhattan = search_with(&tiled, &rules, &Heuristic::Manhattan).unwrap();
+    assert_eq!(zero.route.risk, manhattan.route.risk, "{:?}", moves);
+    assert_follows_rules(&tiled, &rules, &manhattan.route);
+  }
+}
@@ -0,0 +1,53 @@
+use adventofcode_2021::day02::Day02;
+use adventofcode_2021::day13::Day13;
+use adventofcode_2021::registry;
+use adventofcode_2021::{AocError, ParseError, Solution};
+
+fn parse_error<S: Solution>(input: &str) -> ParseError {
+  match S::parse(input) {
+    Err(AocError::Parse(err)) => err,
+    Err(err) => panic!("expected a parse error, got {}", err),
+    Ok(_) => panic!("expected a parse error"),
+  }
+}
+
+#[test]
+fn error_position() {
+  let err = parse_error::<Day02>("forward 5\ndown 5\nsideways 3\n");
+  assert_eq!((err.line, err.column), (3, 1));
+  assert_eq!(err.text, "sideways 3");
+  assert_eq!(err.token, "sideways");
+}
+
+#[test]
+fn error_column_counts_characters() {
+  let err = parse_error::<Day13>("1,2\n3,ü\n\nfold along y=3\n");
+  assert_eq!((err.line, err.column), (2, 3));
+  assert_eq!(err.token, "ü");
+}
+
+#[test]
+fn error_at_end() {
+  let err = parse_error::<Day13>("1,2");
+  assert_eq!((err.line, err.column), (1, 4));
+  assert_eq!(err.expected, "folds");
+}
+
+#[test]
+fn diagnostic() {
+  let err = parse_error::<Day13>("1,2\n3,x\n\nfold along y=3\n").with_day(13);
+  assert_eq!(
+    err.to_string(),
+    "day 13, line 2, column 3: expected a coordinate, found `x`\n  \
+     |\n2 | 3,x\n  |   ^"
+  );
+}
+
+#[test]
+fn registry_attaches_day() {
+  let day = registry::find(13).unwrap();
+  match day.solve(1, "1,2\n3,x\n") {
+    Err(AocError::Parse(err)) => assert_eq!(err.day, Some(13)),
+    _ => panic!("expected a parse error"),
+  }
+}
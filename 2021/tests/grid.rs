@@ -0,0 +1,47 @@
+use adventofcode_2021::grid::Grid;
+
+fn grid() -> Grid<u8> {
+  Grid::parse_digits("123\n456\n789\n000\n").unwrap()
+}
+
+#[test]
+fn size_and_indexing() {
+  let grid = grid();
+  assert_eq!((grid.width(), grid.height()), (3, 4));
+  assert_eq!(grid[(1, 2)], 6);
+  assert_eq!(grid.get((3, 0)), Some(&0));
+  assert_eq!(grid.get((4, 0)), None);
+  assert_eq!(grid.get((0, 3)), None);
+}
+
+#[test]
+fn rows_and_columns() {
+  let grid = grid();
+  assert_eq!(grid.row(2), [7, 8, 9]);
+  assert_eq!(grid.rows().count(), 4);
+  assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5, 8, 0]);
+  let sums: Vec<u32> = grid
+    .columns()
+    .map(|column| column.map(|&c| c as u32).sum())
+    .collect();
+  assert_eq!(sums, [12, 15, 18]);
+}
+
+#[test]
+fn neighbours() {
+  let grid = grid();
+  let four: Vec<_> = grid.neighbours4((0, 0)).collect();
+  assert_eq!(four, [(0, 1), (1, 0)]);
+  assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+
+  let eight: Vec<_> = grid.neighbours8((3, 2)).collect();
+  assert_eq!(eight, [(2, 1), (2, 2), (3, 1)]);
+  assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+}
+
+#[test]
+fn display() {
+  let grid = grid().map(|&d| if d > 4 { '#' } else { '.' });
+  assert_eq!(grid.to_string(), "...\n.##\n###\n...\n");
+  assert_eq!(Grid::from_fn(2, 1, |(_, col)| col).to_string(), "01\n");
+}
@@ -0,0 +1,64 @@
+use adventofcode_2021::grid::Grid;
+use adventofcode_2021::ocr::{self, GLYPHS, GLYPH_HEIGHT, GLYPH_WIDTH};
+use adventofcode_2021::AocError;
+
+fn pixels(rows: &[String]) -> Grid<bool> {
+  let width = rows[0].len();
+  let cells = rows.iter().flat_map(|row| row.chars().map(|c| c == '#'));
+  Grid::new(width, cells.collect())
+}
+
+/// The glyphs of `letters` side by side.
+fn draw(letters: &str) -> Vec<String> {
+  (0..GLYPH_HEIGHT)
+    .map(|row| {
+      letters
+        .chars()
+        .map(|letter| {
+          let (_, glyph) = GLYPHS.iter().find(|(l, _)| *l == letter).unwrap();
+          glyph[row]
+        })
+        .collect()
+    })
+    .collect()
+}
+
+#[test]
+fn alphabet() {
+  let alphabet: String = GLYPHS.iter().map(|(letter, _)| letter).collect();
+  assert_eq!(alphabet, "ABCEFGHIJKLOPRSUYZ");
+  assert_eq!(ocr::read(&pixels(&draw(&alphabet))).unwrap(), alphabet);
+}
+
+#[test]
+fn trailing_blank_column_is_optional() {
+  let mut rows = draw("HI");
+  for row in &mut rows {
+    row.pop();
+  }
+  assert_eq!(rows[0].len(), 2 * GLYPH_WIDTH - 1);
+  assert_eq!(ocr::read(&pixels(&rows)).unwrap(), "HI");
+}
+
+#[test]
+fn unrecognised_glyphs() {
+  let mut rows = draw("ABC");
+  rows[0].replace_range(8..9, "#");
+  rows[5].replace_range(11..12, ".");
+  match ocr::read(&pixels(&rows)) {
+    Err(AocError::NoAnswer(message)) => assert_eq!(
+      message,
+      "unrecognised letters\n\
+       glyph 1:\n####.\n#..#.\n###..\n#..#.\n#..#.\n###..\n\
+       glyph 2:\n.##..\n#..#.\n#....\n#....\n#..#.\n..#.."
+    ),
+    other => panic!("{:?}", other),
+  }
+}
+
+#[test]
+fn too_many_rows() {
+  let mut rows = draw("L");
+  rows.push(".....".to_string());
+  assert!(ocr::read(&pixels(&rows)).is_err());
+}
@@ -0,0 +1,69 @@
+use adventofcode_2021::parse::Parser;
+
+#[test]
+fn sections() {
+  let input = "a\nb\n\n\nc\r\n\r\nd\n";
+  let parser = Parser::new(input);
+  assert_eq!(parser.sections(), ["a\nb", "c", "d"]);
+
+  let err = parser.split_sections(["one", "two"]).unwrap_err();
+  assert_eq!((err.line, err.column), (7, 1));
+  assert_eq!(err.expected, "end of input");
+
+  let err = Parser::new("a\n").split_sections(["a", "b"]).unwrap_err();
+  assert_eq!(err.expected, "b");
+}
+
+#[test]
+fn integers() {
+  let input = "3,4,3,1,2\n";
+  let parser = Parser::new(input);
+  assert_eq!(parser.integers::<u8>(input, "n").unwrap(), [3, 4, 3, 1, 2]);
+
+  let input = "3,4,x,1\n";
+  let err = Parser::new(input).integers::<u8>(input, "n").unwrap_err();
+  assert_eq!((err.column, err.token.as_str()), (5, "x"));
+}
+
+#[test]
+fn edges_and_rules() {
+  let input = "start-A\nCH -> B\nAB\n";
+  let parser = Parser::new(input);
+  let lines: Vec<_> = parser.lines().collect();
+  assert_eq!(parser.edge(lines[0]).unwrap(), ("start", "A"));
+  assert_eq!(parser.rule(lines[1]).unwrap(), ("CH", "B"));
+
+  let err = parser.rule(lines[2]).unwrap_err();
+  assert_eq!((err.line, err.column), (3, 1));
+}
+
+#[test]
+fn words() {
+  let input = "forward 5 up";
+  let parser = Parser::new(input);
+  let err = parser.words(input, ["a", "b"]).unwrap_err();
+  assert_eq!((err.column, err.token.as_str()), (11, "up"));
+
+  let err = parser.words(&input[..9], ["a", "b", "c"]).unwrap_err();
+  assert_eq!((err.column, err.expected.as_str()), (10, "c"));
+}
+
+#[test]
+fn digit_grid() {
+  let input = "123\n456\n";
+  let parser = Parser::new(input);
+  let grid = parser.digit_grid(input, 0..=9, "a digit").unwrap();
+  assert_eq!((grid.width(), grid.height()), (3, 2));
+  assert_eq!(grid.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6]);
+
+  let input = "123\n406\n";
+  let parser = Parser::new(input);
+  let err = parser.digit_grid(input, 1..=9, "a digit").unwrap_err();
+  assert_eq!((err.line, err.column), (2, 2));
+  assert_eq!(err.expected, "a digit from 1 to 9");
+
+  let input = "123\n45\n";
+  let parser = Parser::new(input);
+  let err = parser.digit_grid(input, 0..=9, "a digit").unwrap_err();
+  assert_eq!((err.line, err.token.as_str()), (2, "45"));
+}
@@ -0,0 +1,109 @@
+//! Runs every day against the puzzle example from `data/day-NN-example` and
+//! against the real input in `data/day-NN`, whose answers are kept in
+//! `answers.toml`.
+
+use adventofcode_2021::answers::ExpectedAnswers;
+use adventofcode_2021::Solution;
+use std::fs;
+use std::path::PathBuf;
+
+fn solve<S: Solution>(file_name: &str) -> [String; 2] {
+  let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "data", file_name]
+    .iter()
+    .collect();
+  let input = S::parse(&fs::read_to_string(path).unwrap()).unwrap();
+  [
+    S::part1(&input).unwrap().to_string(),
+    S::part2(&input).unwrap().to_string(),
+  ]
+}
+
+fn expected_answer(day: u8, part: u8) -> String {
+  ExpectedAnswers::load(&ExpectedAnswers::default_path())
+    .unwrap()
+    .get(day, part)
+    .unwrap_or_else(|| panic!("No answer for day {} part {}", day, part))
+    .to_string()
+}
+
+macro_rules! day_tests {
+  ($module:ident, $solution:ident, $day:literal, $example:expr) => {
+    day_tests!(
+      $module,
+      $solution,
+      $day,
+      $example,
+      [expected_answer($day, 1), expected_answer($day, 2)]
+    );
+  };
+  ($module:ident, $solution:ident, $day:literal, $example:expr, $input:expr) => {
+    mod $module {
+      use super::*;
+      use adventofcode_2021::$module::$solution;
+
+      #[test]
+      fn example() {
+        let file_name = format!("day-{:02}-example", $day);
+        assert_eq!(solve::<$solution>(&file_name), $example.map(String::from));
+      }
+
+      #[test]
+      fn input() {
+        let file_name = format!("day-{:02}", $day);
+        assert_eq!(solve::<$solution>(&file_name), $input);
+      }
+    }
+  };
+}
+
+day_tests!(day01, Day01, 1, ["7", "5"]);
+day_tests!(day02, Day02, 2, ["150", "900"]);
+day_tests!(day03, Day03, 3, ["198", "230"]);
+day_tests!(day04, Day04, 4, ["4512", "1924"]);
+day_tests!(day05, Day05, 5, ["5", "12"]);
+day_tests!(day06, Day06, 6, ["5934", "26984457539"]);
+day_tests!(day07, Day07, 7, ["37", "168"]);
+day_tests!(day08, Day08, 8, ["26", "61229"]);
+day_tests!(day09, Day09, 9, ["15", "1134"]);
+day_tests!(day10, Day10, 10, ["26397", "288957"]);
+day_tests!(day11, Day11, 11, ["1656", "195"]);
+day_tests!(day12, Day12, 12, ["10", "36"]);
+
+mod day13 {
+  use super::*;
+  use adventofcode_2021::day13::{apply_fold, render_dots, Day13};
+  use adventofcode_2021::AocError;
+
+  // The example folds into a square, which is no letter.
+  #[test]
+  fn example() {
+    let path = [env!("CARGO_MANIFEST_DIR"), "data", "day-13-example"];
+    let input = fs::read_to_string(path.iter().collect::<PathBuf>()).unwrap();
+    let input = Day13::parse(&input).unwrap();
+    assert_eq!(Day13::part1(&input).unwrap(), 17);
+    match Day13::part2(&input) {
+      Err(AocError::NoAnswer(message)) => assert_eq!(
+        message,
+        "unrecognised letters\nglyph 0:\n#####\n#...#\n#...#\n#...#\n#####\n....."
+      ),
+      other => panic!("{:?}", other),
+    }
+
+    let mut dots = input.dots.clone();
+    for &fold in &input.folds {
+      apply_fold(fold, &mut dots).unwrap();
+    }
+    assert_eq!(render_dots(dots), "#####\n#   #\n#   #\n#   #\n#####");
+  }
+
+  #[test]
+  fn input() {
+    assert_eq!(
+      solve::<Day13>("day-13"),
+      [expected_answer(13, 1), expected_answer(13, 2)]
+    );
+  }
+}
+
+day_tests!(day14, Day14, 14, ["1588", "2188189693529"]);
+day_tests!(day15, Day15, 15, ["40", "315"]);